@@ -0,0 +1,819 @@
+//! The arguments of the attribute, like `#[aidecomment(tag = "users")]`.
+
+use proc_macro2::Span;
+use quote::ToTokens;
+use serde_json::Value;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{
+    Expr, ExprArray, ExprLit, ExprUnary, Ident, Lit, LitStr, Meta, Token, Type, UnOp, Visibility,
+};
+
+use crate::comment::{self, SummaryMode};
+use crate::config;
+use crate::operation::{Case, Merge, OperationDoc, ResponseDoc, Status};
+
+/// The arguments given to the attribute, which take precedence over the doc
+/// comment.
+#[derive(Default)]
+pub(crate) struct Args {
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    /// Whether the whole comment is the description, without a summary.
+    pub description_only: bool,
+    /// Whether the description is left out because it is only for Rust
+    /// developers.
+    pub summary_only: bool,
+    /// Where the summary ends and the description starts.
+    pub summary_mode: Option<SummaryMode>,
+    /// How the summary and description are combined with existing ones.
+    pub merge: Option<Merge>,
+    /// Added around the summary, for groups of operations that should be
+    /// marked the same way.
+    pub summary_prefix: Option<String>,
+    pub summary_suffix: Option<String>,
+    pub tags: Vec<String>,
+    /// Whether the operation is tagged with the name of the module of its
+    /// handler.
+    pub module_tag: bool,
+    /// How the name of the module is written, and what it starts with.
+    pub module_tag_case: Option<Case>,
+    pub module_tag_prefix: Option<String>,
+    pub operation_id: Option<String>,
+    /// Whether the operation id is the name of the handler if it isn't
+    /// documented.
+    pub auto_operation_id: bool,
+    /// Whether JSDoc-style tags like `@param` are understood.
+    pub jsdoc: bool,
+    /// Whether the operation is marked with `x-internal`, since it can't be
+    /// left out of the document from here.
+    pub hidden: bool,
+    /// Who the operation is meant for, which is one of [`AUDIENCES`].
+    pub audience: Option<String>,
+    /// Why the operation is deprecated, which may be empty, if it is.
+    pub deprecated: Option<String>,
+    /// Alternative security requirements, each of which lists the schemes
+    /// and their scopes that are needed together.
+    pub security: Vec<Vec<(String, Vec<String>)>>,
+    /// A shorthand for a common kind of authentication.
+    pub auth: Option<Auth>,
+    pub responses: Vec<ResponseDoc>,
+    /// The type the handler responds with, if it can't be named in the
+    /// signature.
+    pub response_ty: Option<Type>,
+    /// An error enum that derives `ErrorResponses`.
+    pub error: Option<Type>,
+    /// Extractor types that derive `AideCommentParams`.
+    pub params: Vec<Type>,
+    pub consumes: Vec<String>,
+    pub produces: Vec<String>,
+    /// The URL of further documentation along with its description.
+    pub external_docs: Option<(String, Option<String>)>,
+    /// Alternative base URLs along with their descriptions.
+    pub servers: Vec<(String, Option<String>)>,
+    /// Vendor extensions, keyed with their `x-` prefix.
+    pub extensions: Vec<(String, Value)>,
+    /// A function taking and returning an aide `TransformOperation`, which is
+    /// called after the documentation is applied.
+    pub transform: Option<Expr>,
+    /// The name of the generated struct, instead of `{handler}_AideComment`.
+    pub struct_name: Option<Ident>,
+    /// The visibility of the generated struct, instead of the handler's.
+    pub vis: Option<Visibility>,
+    /// An extractor of the handler that gets the `OperationInput`
+    /// implementation, instead of a generated struct.
+    pub attach: Option<Type>,
+    /// Whether the arguments of the handler are left alone, so that the
+    /// documentation is only applied by the `docs` function of the struct.
+    pub transform_only: bool,
+    /// Another handler whose summary and description are used as well.
+    pub inherit: Option<syn::Path>,
+    /// A `&str` constant that is documentation shared between handlers.
+    pub use_const: Option<syn::Path>,
+    /// A markdown file, relative to the source file, that continues the doc
+    /// comment.
+    pub file: Option<LitStr>,
+    /// Where the `aide` crate is, if it is renamed or re-exported.
+    pub aide: Option<syn::Path>,
+    /// Where the `axum` crate is, if it is renamed or re-exported.
+    pub axum: Option<syn::Path>,
+    /// Whether what the comment was parsed into and the generated code are
+    /// printed while building.
+    pub debug: bool,
+    /// Documentation for one of the methods that the handler is routed for.
+    pub methods: Vec<MethodDoc>,
+    /// The predicate that the documentation is only generated under, like
+    /// `feature = "docs"`.
+    pub cfg: Option<proc_macro2::TokenStream>,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = Args::default();
+
+        while !input.is_empty() {
+            // types can have generics, which a `Meta` can't
+            let fork = input.fork();
+            let is_type = fork.parse::<Ident>().is_ok_and(|ident| {
+                TYPE_ARGUMENTS.iter().any(|name| ident == name) && fork.peek(Token![=])
+            });
+
+            if is_type {
+                let ident = input.parse::<Ident>()?;
+                input.parse::<Token![=]>()?;
+                let ty = input.parse::<Type>()?;
+                match ident.to_string().as_str() {
+                    "response_ty" => args.response_ty = Some(ty),
+                    "error" => args.error = Some(ty),
+                    "params" => args.params.push(ty),
+                    _ => args.attach = Some(ty),
+                }
+            } else {
+                args.argument(input.parse()?)?;
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(args)
+    }
+}
+
+/// The audiences that an operation can be meant for.
+const AUDIENCES: &[&str] = &["internal", "public", "partner"];
+
+/// The arguments that are set to a type.
+const TYPE_ARGUMENTS: &[&str] = &["response_ty", "error", "params", "attach"];
+
+impl Args {
+    /// Sets an argument that isn't set to a type.
+    fn argument(&mut self, meta: Meta) -> syn::Result<()> {
+        let path = meta.path();
+        if path.is_ident("summary") {
+            self.summary = Some(string(&meta)?);
+        } else if path.is_ident("description_only") {
+            meta.require_path_only()?;
+            self.description_only = true;
+        } else if path.is_ident("summary_only") {
+            meta.require_path_only()?;
+            self.summary_only = true;
+        } else if path.is_ident("summary_mode") {
+            let value = string(&meta)?;
+            self.summary_mode = Some(SummaryMode::parse(&value).ok_or_else(|| {
+                syn::Error::new_spanned(
+                    &meta,
+                    format!("unknown summary mode `{value}`, expected `first_paragraph` or `first_sentence`"),
+                )
+            })?);
+        } else if path.is_ident("merge") {
+            let value = string(&meta)?;
+            self.merge = Some(Merge::parse(&value).ok_or_else(|| {
+                syn::Error::new_spanned(
+                    &meta,
+                    format!("unknown merge `{value}`, expected `replace`, `append`, or `fill`"),
+                )
+            })?);
+        } else if path.is_ident("summary_prefix") {
+            self.summary_prefix = Some(string(&meta)?);
+        } else if path.is_ident("summary_suffix") {
+            self.summary_suffix = Some(string(&meta)?);
+        } else if path.is_ident("description") {
+            self.description = Some(string(&meta)?);
+        } else if path.is_ident("tag") {
+            self.tags.push(string(&meta)?);
+        } else if path.is_ident("module_tag") {
+            self.module_tag = true;
+            if let Meta::List(_) = meta {
+                for meta in nested(&meta)? {
+                    let path = meta.path();
+                    if path.is_ident("case") {
+                        let value = string(&meta)?;
+                        self.module_tag_case = Some(Case::parse(&value).ok_or_else(|| {
+                            syn::Error::new_spanned(
+                                &meta,
+                                format!("unknown case `{value}`, expected `snake`, `kebab`, `title`, or `pascal`"),
+                            )
+                        })?);
+                    } else if path.is_ident("prefix") {
+                        self.module_tag_prefix = Some(string(&meta)?);
+                    } else {
+                        return Err(unknown(path, "`case` or `prefix`"));
+                    }
+                }
+            } else {
+                meta.require_path_only()?;
+            }
+        } else if path.is_ident("operation_id") {
+            self.operation_id = Some(string(&meta)?);
+        } else if path.is_ident("hidden") {
+            meta.require_path_only()?;
+            self.hidden = true;
+        } else if path.is_ident("method") {
+            let method = method(&meta)?;
+            if self
+                .methods
+                .iter()
+                .any(|existing| existing.method == method.method)
+            {
+                return Err(syn::Error::new_spanned(
+                    &meta,
+                    format!("`{}` is already documented", method.method),
+                ));
+            }
+            self.methods.push(method);
+        } else if path.is_ident("audience") {
+            let audience = string(&meta)?;
+            if !AUDIENCES.contains(&audience.as_str()) {
+                return Err(syn::Error::new_spanned(
+                    &meta,
+                    format!("unknown audience `{audience}`, expected `internal`, `public`, or `partner`"),
+                ));
+            }
+            self.audience = Some(audience);
+        } else if path.is_ident("transform_only") {
+            meta.require_path_only()?;
+            self.transform_only = true;
+        } else if path.is_ident("debug") {
+            meta.require_path_only()?;
+            self.debug = true;
+        } else if path.is_ident("cfg") {
+            // a string is the name of a feature, which is the common case
+            self.cfg = Some(match &meta {
+                Meta::List(list) => list.tokens.clone(),
+                _ => {
+                    let feature = string(&meta)?;
+                    quote::quote!(feature = #feature)
+                }
+            });
+        } else if path.is_ident("deprecated") {
+            // the note is optional, like with `#[deprecated]`
+            self.deprecated = Some(match meta {
+                Meta::Path(_) => String::new(),
+                _ => string(&meta)?,
+            });
+        } else if path.is_ident("security") {
+            let requirement = meta.require_list()?.parse_args_with(security_requirement)?;
+            self.security.push(requirement);
+        } else if path.is_ident("auth") {
+            let value = string(&meta)?;
+            let auth = Auth::parse(&value).ok_or_else(|| {
+                syn::Error::new_spanned(
+                    &meta,
+                    format!(
+                        "unknown auth `{value}`, expected `bearer`, `api_key`, `basic`, or `none`"
+                    ),
+                )
+            })?;
+            self.auth = Some(auth);
+        } else if path.is_ident("response") {
+            self.responses.push(response(&meta)?);
+        } else if path.is_ident("consumes") {
+            self.consumes.push(string(&meta)?);
+        } else if path.is_ident("produces") {
+            self.produces.push(string(&meta)?);
+        } else if path.is_ident("external_docs") {
+            self.external_docs = Some(link(&meta)?);
+        } else if path.is_ident("server") {
+            self.servers.push(link(&meta)?);
+        } else if path.is_ident("servers") {
+            let urls = meta
+                .require_list()?
+                .parse_args_with(Punctuated::<LitStr, Token![,]>::parse_terminated)?;
+            self.servers
+                .extend(urls.iter().map(|url| (url.value(), None)));
+        } else if path.is_ident("extensions") {
+            for meta in nested(&meta)? {
+                self.extensions.push(extension(&meta)?);
+            }
+        } else if path.is_ident("transform") {
+            self.transform = Some(meta.require_name_value()?.value.clone());
+        } else if path.is_ident("struct_name") {
+            let name = string(&meta)?;
+            let ident = syn::parse_str::<Ident>(&name).map_err(|_| {
+                syn::Error::new_spanned(&meta, format!("`{name}` isn't a valid identifier"))
+            })?;
+            let span = meta.require_name_value()?.value.span();
+            self.struct_name = Some(Ident::new(&ident.to_string(), span));
+        } else if path.is_ident("vis") {
+            let vis = string(&meta)?;
+            self.vis = Some(syn::parse_str(&vis).map_err(|_| {
+                syn::Error::new_spanned(&meta, format!("`{vis}` isn't a valid visibility"))
+            })?);
+        } else if path.is_ident("inherit") {
+            self.inherit = Some(path_value(&meta)?);
+        } else if path.is_ident("use_const") {
+            self.use_const = Some(path_value(&meta)?);
+        } else if path.is_ident("file") {
+            self.file = Some(LitStr::new(&string(&meta)?, meta.span()));
+        } else if path.is_ident("aide") {
+            self.aide = Some(path_value(&meta)?);
+        } else if path.is_ident("axum") {
+            self.axum = Some(path_value(&meta)?);
+        } else if path.is_ident("auto_operation_id") {
+            meta.require_path_only()?;
+            self.auto_operation_id = true;
+        } else if path.is_ident("jsdoc") {
+            meta.require_path_only()?;
+            self.jsdoc = true;
+        } else {
+            let name = path.to_token_stream().to_string();
+            return Err(syn::Error::new_spanned(
+                path,
+                format!("unknown argument `{name}`"),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Where the summary ends, from the argument or the crate-wide
+    /// configuration.
+    pub(crate) fn summary_mode(&self) -> syn::Result<SummaryMode> {
+        if let Some(mode) = self.summary_mode {
+            return Ok(mode);
+        }
+
+        match config::summary_mode() {
+            Some(mode) => SummaryMode::parse(&mode).ok_or_else(|| {
+                syn::Error::new(
+                    Span::call_site(),
+                    format!("`AIDECOMMENT_SUMMARY_MODE` should be `first_paragraph` or `first_sentence`, found `{mode}`"),
+                )
+            }),
+            None => Ok(SummaryMode::default()),
+        }
+    }
+
+    /// The case and prefix of the tag named after the module of the handler,
+    /// from the argument or the crate-wide configuration, if it is tagged
+    /// with one.
+    pub(crate) fn module_tag(&self) -> syn::Result<Option<(Case, String)>> {
+        if !self.module_tag && !config::module_tags() {
+            return Ok(None);
+        }
+
+        let case = match (self.module_tag_case, config::module_tag_case()) {
+            (Some(case), _) => case,
+            (None, Some(case)) => Case::parse(&case).ok_or_else(|| {
+                syn::Error::new(
+                    Span::call_site(),
+                    format!("`AIDECOMMENT_MODULE_TAG_CASE` should be `snake`, `kebab`, `title`, or `pascal`, found `{case}`"),
+                )
+            })?,
+            (None, None) => Case::default(),
+        };
+        let prefix = self
+            .module_tag_prefix
+            .clone()
+            .or_else(config::module_tag_prefix)
+            .unwrap_or_default();
+
+        Ok(Some((case, prefix)))
+    }
+
+    /// The path of the `aide` or `axum` crate, from the argument or the
+    /// crate-wide configuration.
+    pub(crate) fn crate_path(&self, name: &str) -> syn::Result<syn::Path> {
+        let path = match name {
+            "aide" => &self.aide,
+            _ => &self.axum,
+        };
+        if let Some(path) = path {
+            return Ok(path.clone());
+        }
+
+        match config::crate_path(name) {
+            Some((variable, path)) => syn::parse_str(&path).map_err(|_| {
+                syn::Error::new(
+                    Span::call_site(),
+                    format!("`{variable}` should be a path, found `{path}`"),
+                )
+            }),
+            None => {
+                let name = Ident::new(name, Span::call_site());
+                Ok(syn::parse_quote!(::#name))
+            }
+        }
+    }
+
+    /// Applies the arguments on top of the documentation from the comment of
+    /// the handler with the given name.
+    pub(crate) fn apply(self, doc: &mut OperationDoc, handler: &str) {
+        // the summary comes from the other handler, so all of the comment
+        // adds to its description
+        if self.inherit.is_some() || self.description_only {
+            let summary = std::mem::take(&mut doc.summary);
+            doc.description = format!("{summary}\n\n{}", doc.description)
+                .trim()
+                .to_owned();
+        }
+
+        if self.description_only && self.summary.is_none() {
+            doc.omit_summary = true;
+        }
+        if self.summary_only && self.description.is_none() {
+            doc.omit_description = true;
+        }
+        if let Some(summary) = self.summary {
+            doc.summary = summary;
+        }
+        if let Some(description) = self.description {
+            doc.description = description;
+        }
+        if let Some(merge) = self.merge {
+            doc.merge = merge;
+        }
+
+        for tag in self.tags {
+            if !doc.tags.contains(&tag) {
+                doc.tags.push(tag);
+            }
+        }
+
+        if let Some(operation_id) = self.operation_id {
+            doc.operation_id = Some(operation_id);
+        }
+        if (self.auto_operation_id || config::auto_operation_id()) && doc.operation_id.is_none() {
+            doc.operation_id = Some(handler.to_owned());
+        }
+
+        if let Some(note) = self.deprecated {
+            doc.deprecated = Some(note);
+        }
+
+        if let Some(auth) = self.auth {
+            doc.security = vec![auth.requirement()];
+            doc.description = format!("{}\n\n{}", doc.description, auth.sentence())
+                .trim()
+                .to_owned();
+        }
+        if !self.security.is_empty() {
+            doc.security = self.security;
+        }
+
+        for response in self.responses {
+            doc.responses
+                .retain(|existing| existing.status != response.status);
+            doc.responses.push(response);
+        }
+
+        if !self.consumes.is_empty() {
+            doc.consumes = self.consumes;
+        }
+        if !self.produces.is_empty() {
+            doc.produces = self.produces;
+        }
+
+        if let Some(external_docs) = self.external_docs {
+            doc.external_docs = Some(external_docs);
+        }
+
+        if !self.servers.is_empty() {
+            doc.servers = self.servers;
+        }
+
+        for (key, value) in self.extensions {
+            doc.extensions.retain(|(existing, _)| *existing != key);
+            doc.extensions.push((key, value));
+        }
+
+        if let Some(audience) = self.audience {
+            doc.extensions.retain(|(key, _)| key != "x-audience");
+            doc.extensions
+                .push(("x-audience".to_owned(), audience.into()));
+        }
+
+        // operations for other audiences than the one the document is for are
+        // hidden, whether they were marked here or in the doc comment
+        let excluded = config::audience().is_some_and(|included| {
+            doc.extensions
+                .iter()
+                .find(|(key, _)| key == "x-audience")
+                .is_some_and(|(_, audience)| {
+                    let audiences = match audience {
+                        Value::Array(audiences) => audiences.as_slice(),
+                        audience => std::slice::from_ref(audience),
+                    };
+                    !audiences
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .any(|audience| included.iter().any(|included| included == audience))
+                })
+        });
+
+        if (self.hidden || excluded) && !doc.extensions.iter().any(|(key, _)| key == "x-internal") {
+            doc.extensions.push(("x-internal".to_owned(), true.into()));
+        }
+    }
+}
+
+/// What a `method(HEAD, summary = "...")` argument documents differently for
+/// one method.
+pub(crate) struct MethodDoc {
+    /// The field of the path item, like `head`.
+    pub method: Ident,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub operation_id: Option<String>,
+    pub deprecated: bool,
+}
+
+/// A kind of authentication that the `auth` argument stands for.
+#[derive(Clone, Copy)]
+pub(crate) enum Auth {
+    Bearer,
+    ApiKey,
+    Basic,
+    None,
+}
+
+impl Auth {
+    fn parse(text: &str) -> Option<Auth> {
+        match text {
+            "bearer" => Some(Auth::Bearer),
+            "api_key" => Some(Auth::ApiKey),
+            "basic" => Some(Auth::Basic),
+            "none" => Some(Auth::None),
+            _ => None,
+        }
+    }
+
+    /// The security requirement, using conventional names for the schemes.
+    fn requirement(self) -> Vec<(String, Vec<String>)> {
+        let scheme = match self {
+            Auth::Bearer => "bearer_auth",
+            Auth::ApiKey => "api_key",
+            Auth::Basic => "basic_auth",
+            // an empty requirement means that no authentication is needed
+            Auth::None => return Vec::new(),
+        };
+
+        vec![(scheme.to_owned(), Vec::new())]
+    }
+
+    /// Explains the authentication, for the description.
+    fn sentence(self) -> &'static str {
+        match self {
+            Auth::Bearer => "Requires a bearer token in the `Authorization` header.",
+            Auth::ApiKey => "Requires an API key.",
+            Auth::Basic => "Requires HTTP basic authentication.",
+            Auth::None => "Doesn't require authentication.",
+        }
+    }
+}
+
+/// The path of an argument like `aide = path`.
+fn path_value(meta: &Meta) -> syn::Result<syn::Path> {
+    match comment::ungroup(&meta.require_name_value()?.value) {
+        Expr::Path(path) => Ok(path.path.clone()),
+        value => Err(syn::Error::new_spanned(value, "expected a path")),
+    }
+}
+
+/// The string of a `name = "value"` argument.
+pub(crate) fn string(meta: &Meta) -> syn::Result<String> {
+    match comment::ungroup(&meta.require_name_value()?.value) {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(value),
+            ..
+        }) => Ok(value.value()),
+        value => Err(syn::Error::new_spanned(value, "expected a string")),
+    }
+}
+
+/// Parses a `method(HEAD, summary = "...")` argument.
+fn method(meta: &Meta) -> syn::Result<MethodDoc> {
+    let mut nested = nested(meta)?.into_iter();
+    let method = match nested.next() {
+        Some(Meta::Path(path)) => path
+            .get_ident()
+            .map(|ident| ident.to_string().to_lowercase())
+            .filter(|method| METHODS.contains(&method.as_str()))
+            .map(|method| Ident::new(&method, path.span()))
+            .ok_or_else(|| {
+                syn::Error::new_spanned(&path, "expected a method like `GET` or `HEAD`")
+            })?,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                meta,
+                "`method` needs a method like `GET` or `HEAD` first",
+            ))
+        }
+    };
+
+    let mut doc = MethodDoc {
+        method,
+        summary: None,
+        description: None,
+        operation_id: None,
+        deprecated: false,
+    };
+    for meta in nested {
+        let path = meta.path();
+        if path.is_ident("summary") {
+            doc.summary = Some(string(&meta)?);
+        } else if path.is_ident("description") {
+            doc.description = Some(string(&meta)?);
+        } else if path.is_ident("operation_id") {
+            doc.operation_id = Some(string(&meta)?);
+        } else if path.is_ident("deprecated") {
+            meta.require_path_only()?;
+            doc.deprecated = true;
+        } else {
+            return Err(unknown(
+                path,
+                "`summary`, `description`, `operation_id`, or `deprecated`",
+            ));
+        }
+    }
+
+    Ok(doc)
+}
+
+/// The methods of a path item.
+pub(crate) const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Parses a `response(status = 404, description = "...")` argument.
+fn response(meta: &Meta) -> syn::Result<ResponseDoc> {
+    let mut status = None;
+    let mut description = None;
+
+    for meta in nested(meta)? {
+        let path = meta.path();
+        if path.is_ident("status") {
+            // either a code like `404` or a string like `"4XX"`
+            let value = comment::ungroup(&meta.require_name_value()?.value);
+            let text = match value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Int(code),
+                    ..
+                }) => code.base10_digits().to_owned(),
+                _ => string(&meta)?,
+            };
+            status = Some(Status::parse(&text).ok_or_else(|| {
+                syn::Error::new_spanned(value, format!("invalid status `{text}`"))
+            })?);
+        } else if path.is_ident("description") {
+            description = Some(string(&meta)?);
+        } else {
+            return Err(unknown(path, "`status` or `description`"));
+        }
+    }
+
+    let Some(status) = status else {
+        return Err(syn::Error::new_spanned(meta, "`response` needs a `status`"));
+    };
+
+    Ok(ResponseDoc {
+        status,
+        description: description.unwrap_or_default(),
+    })
+}
+
+/// Parses a `name(url = "...", description = "...")` argument, like
+/// `external_docs` or `server`.
+pub(crate) fn link(meta: &Meta) -> syn::Result<(String, Option<String>)> {
+    let mut url = None;
+    let mut description = None;
+
+    for meta in nested(meta)? {
+        let path = meta.path();
+        if path.is_ident("url") {
+            url = Some(string(&meta)?);
+        } else if path.is_ident("description") {
+            description = Some(string(&meta)?);
+        } else {
+            return Err(unknown(path, "`url` or `description`"));
+        }
+    }
+
+    let Some(url) = url else {
+        let name = meta.path().to_token_stream().to_string();
+        return Err(syn::Error::new_spanned(
+            meta,
+            format!("`{name}` needs a `url`"),
+        ));
+    };
+
+    Ok((url, description))
+}
+
+/// Parses an `x_name = value` item of an `extensions(...)` argument, where the
+/// underscores in the name are dashes in the extension.
+fn extension(meta: &Meta) -> syn::Result<(String, Value)> {
+    let value = &meta.require_name_value()?.value;
+    let path = meta.path();
+    let Some(ident) = path.get_ident() else {
+        return Err(syn::Error::new_spanned(
+            path,
+            "expected a name like `x_owner`",
+        ));
+    };
+
+    let key = ident.to_string().replace('_', "-");
+    if !key.starts_with("x-") {
+        return Err(syn::Error::new_spanned(
+            ident,
+            format!("extensions must start with `x_`, found `{ident}`"),
+        ));
+    }
+
+    Ok((key, json_value(value)?))
+}
+
+/// Converts a literal, or an array of them, into JSON.
+fn json_value(expr: &Expr) -> syn::Result<Value> {
+    let value = match comment::ungroup(expr) {
+        Expr::Lit(ExprLit { lit, .. }) => match lit {
+            Lit::Str(value) => value.value().into(),
+            Lit::Bool(value) => value.value.into(),
+            Lit::Int(value) => value.base10_parse::<i64>()?.into(),
+            Lit::Float(value) => value.base10_parse::<f64>()?.into(),
+            _ => return Err(syn::Error::new_spanned(lit, "unsupported literal")),
+        },
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => match json_value(expr)? {
+            Value::Number(number) if number.is_i64() => (-number.as_i64().unwrap()).into(),
+            Value::Number(number) => (-number.as_f64().unwrap()).into(),
+            _ => return Err(syn::Error::new_spanned(expr, "expected a number")),
+        },
+        Expr::Array(ExprArray { elems, .. }) => {
+            Value::Array(elems.iter().map(json_value).collect::<syn::Result<_>>()?)
+        }
+        _ => {
+            return Err(syn::Error::new_spanned(
+                expr,
+                "expected a string, number, boolean, or an array of them",
+            ))
+        }
+    };
+
+    Ok(value)
+}
+
+/// Parses the schemes of a `security("scheme", scopes("scope"))` argument,
+/// where the scopes belong to the scheme before them.
+fn security_requirement(input: ParseStream) -> syn::Result<Vec<(String, Vec<String>)>> {
+    let mut schemes: Vec<(String, Vec<String>)> = Vec::new();
+
+    while !input.is_empty() {
+        if input.peek(LitStr) {
+            let scheme = input.parse::<LitStr>()?;
+            schemes.push((scheme.value(), Vec::new()));
+        } else {
+            let ident = input.parse::<Ident>()?;
+            if ident != "scopes" {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "expected a scheme like `\"bearer_auth\"` or `scopes(...)`",
+                ));
+            }
+            let Some((_, scopes)) = schemes.last_mut() else {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "`scopes` needs a scheme before it",
+                ));
+            };
+
+            let content;
+            syn::parenthesized!(content in input);
+            let values = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+            scopes.extend(values.iter().map(LitStr::value));
+        }
+
+        if input.is_empty() {
+            break;
+        }
+        input.parse::<Token![,]>()?;
+    }
+
+    if schemes.is_empty() {
+        return Err(input.error("expected a security scheme"));
+    }
+
+    Ok(schemes)
+}
+
+/// The arguments in a list like `response(...)`.
+fn nested(meta: &Meta) -> syn::Result<Punctuated<Meta, Token![,]>> {
+    meta.require_list()?
+        .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+}
+
+/// The error for an argument in a list that isn't one of the expected ones.
+pub(crate) fn unknown(path: &syn::Path, expected: &str) -> syn::Error {
+    let name = path.to_token_stream().to_string();
+    syn::Error::new_spanned(
+        path,
+        format!("unknown argument `{name}`, expected {expected}"),
+    )
+}
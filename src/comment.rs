@@ -0,0 +1,1046 @@
+//! Splitting a doc comment into its summary, description, and sections.
+
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Attribute, Expr, ExprLit, Lit, LitStr, Meta, Token};
+
+use crate::config;
+
+/// A single line of a doc comment along with the span of the `#[doc]`
+/// attribute it came from.
+#[derive(Clone)]
+pub(crate) struct Line {
+    pub text: String,
+    pub span: Span,
+}
+
+/// A `# Heading` section of a doc comment.
+pub(crate) struct Section {
+    pub heading: Line,
+    pub name: String,
+    pub lines: Vec<Line>,
+}
+
+/// A doc comment split into the parts that are relevant for an operation.
+///
+/// The summary is the first paragraph. The `body` is everything up to the
+/// first heading and the `sections` are everything after, split by top-level
+/// headings.
+pub(crate) struct DocComment {
+    /// A ```` ```yaml ```` code block at the very start, if there is one.
+    pub front_matter: Option<FrontMatter>,
+    /// Lines like `@x-audit-level: high` and `<!-- aide: tag=admin -->`
+    /// comments, wherever they are.
+    pub directives: Vec<Directive>,
+    /// JSDoc-style tags like `@param id the id of the user`, if they are
+    /// understood.
+    pub tags: Vec<Tag>,
+    pub summary: String,
+    pub body: Vec<Line>,
+    pub sections: Vec<Section>,
+}
+
+/// The contents of a front matter code block.
+pub(crate) struct FrontMatter {
+    pub content: String,
+    pub span: Span,
+}
+
+/// A line like `@x-audit-level: high`, or an entry in a
+/// `<!-- aide: tag=admin -->` comment.
+pub(crate) struct Directive {
+    pub name: String,
+    /// The text after the `:`, which is empty if there isn't one.
+    pub value: String,
+    pub span: Span,
+}
+
+/// A JSDoc-style tag, which may continue onto the following lines.
+pub(crate) struct Tag {
+    pub name: String,
+    pub text: String,
+    pub span: Span,
+}
+
+/// The JSDoc-style tags that are understood.
+const TAGS: &[&str] = &[
+    "param",
+    "arg",
+    "argument",
+    "query",
+    "path",
+    "header",
+    "cookie",
+    "returns",
+    "return",
+    "throws",
+    "response",
+    "deprecated",
+    "tags",
+    "tag",
+    "since",
+    "see",
+    "operationId",
+];
+
+/// The expression inside the invisible groups that `macro_rules!` wraps its
+/// fragments in, like the `$doc` of `#[doc = $doc]`.
+pub(crate) fn ungroup(mut expr: &Expr) -> &Expr {
+    while let Expr::Group(group) = expr {
+        expr = &group.expr;
+    }
+    expr
+}
+
+/// Collects the lines of all `#[doc = "..."]` attributes, including ones
+/// like `#[doc = include_str!("...")]` that are built with macros.
+pub(crate) fn doc_lines(attrs: &[Attribute]) -> syn::Result<Vec<Line>> {
+    let values = attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(nvmeta) => Some(nvmeta),
+            _ => None,
+        })
+        .filter(|nvmeta| nvmeta.path.is_ident("doc"));
+
+    let mut lines = Vec::new();
+    for nvmeta in values {
+        let Some(value) = evaluate(&nvmeta.value)? else {
+            continue;
+        };
+        let span = nvmeta.value.span();
+
+        // an empty string still counts as a (blank) line
+        let mut text = value.lines().map(str::to_owned).collect::<Vec<_>>();
+        if text.is_empty() {
+            text.push(String::new());
+        }
+        lines.extend(text.into_iter().map(|text| Line { text, span }));
+    }
+
+    expand(lines)
+}
+
+/// Expands the snippets and placeholders of the lines of a doc comment.
+pub(crate) fn expand(lines: Vec<Line>) -> syn::Result<Vec<Line>> {
+    // code blocks are written as they are, like examples of placeholders
+    let mut in_fence = false;
+    include_snippets(lines)?
+        .into_iter()
+        .map(|line| {
+            let trimmed = line.text.trim();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_fence = !in_fence;
+            }
+            if in_fence {
+                return Ok(line);
+            }
+            Ok(Line {
+                text: interpolate(&line.text, line.span)?,
+                span: line.span,
+            })
+        })
+        .collect()
+}
+
+/// The start of a directive that includes a snippet.
+const INCLUDE: &str = "{{include:";
+
+/// Replaces the `{{include:name}}` directives in the lines with the snippets
+/// they name, which are markdown files in the snippets directory.
+fn include_snippets(lines: Vec<Line>) -> syn::Result<Vec<Line>> {
+    let mut included = Vec::new();
+    for line in lines {
+        if !line.text.contains(INCLUDE) {
+            included.push(line);
+            continue;
+        }
+        let text = expand_snippets(&line.text, line.span, &mut Vec::new())?;
+        // the lines of a snippet are indented like the line of the directive,
+        // which is usually the space after `///`
+        let indent = &line.text[..line.text.len() - line.text.trim_start().len()];
+        for (index, text) in text.lines().enumerate() {
+            let text = if index == 0 || text.is_empty() {
+                text.to_owned()
+            } else {
+                format!("{indent}{text}")
+            };
+            included.push(Line {
+                text,
+                span: line.span,
+            });
+        }
+    }
+    Ok(included)
+}
+
+/// Expands the snippets in some text, which can include other snippets as
+/// long as they don't include themselves.
+fn expand_snippets(text: &str, span: Span, including: &mut Vec<String>) -> syn::Result<String> {
+    let mut expanded = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(INCLUDE) {
+        let Some(end) = rest[start..].find("}}").map(|end| start + end) else {
+            break;
+        };
+        let name = rest[start + INCLUDE.len()..end].trim();
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '/'))
+        {
+            return Err(syn::Error::new(
+                span,
+                format!("`{name}` isn't a valid snippet name"),
+            ));
+        }
+        if including.iter().any(|snippet| snippet == name) {
+            return Err(syn::Error::new(
+                span,
+                format!("the snippet `{name}` includes itself"),
+            ));
+        }
+
+        let file = config::snippets_dir().join(format!("{name}.md"));
+        let snippet = read_file(&file).map_err(|error| {
+            syn::Error::new(
+                span,
+                format!(
+                    "can't read the snippet `{name}` from `{}`: {error}",
+                    file.display()
+                ),
+            )
+        })?;
+        including.push(name.to_owned());
+        let snippet = expand_snippets(snippet.trim(), span, including)?;
+        including.pop();
+
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&snippet);
+        rest = &rest[end + 2..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Replaces the placeholders that are known at compile time, which are
+/// `{crate_name}`, `{crate_version}`, and `{env:NAME}`, and leaves any other
+/// braces alone. A placeholder in double braces like `{{crate_version}}` is
+/// written as it is, with single braces.
+fn interpolate(text: &str, span: Span) -> syn::Result<String> {
+    let mut interpolated = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        interpolated.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(escaped) = rest.strip_prefix("{{") {
+            if let Some(end) = escaped.find("}}") {
+                if variable(&escaped[..end]).is_some() {
+                    interpolated.push_str(&rest[1..end + 3]);
+                    rest = &escaped[end + 2..];
+                    continue;
+                }
+            }
+        }
+
+        let end = rest.find('}');
+        match end.and_then(|end| Some((end, variable(&rest[1..end])?))) {
+            Some((end, variable)) => {
+                let value = config::var(variable).ok_or_else(|| {
+                    syn::Error::new(
+                        span,
+                        format!(
+                            "environment variable `{variable}` isn't set for `{}`",
+                            &rest[..=end]
+                        ),
+                    )
+                })?;
+                interpolated.push_str(&value);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                interpolated.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    interpolated.push_str(rest);
+    Ok(interpolated)
+}
+
+/// The environment variable that a placeholder is replaced with.
+fn variable(placeholder: &str) -> Option<&str> {
+    match placeholder {
+        "crate_name" => Some("CARGO_PKG_NAME"),
+        "crate_version" => Some("CARGO_PKG_VERSION"),
+        _ => placeholder.strip_prefix("env:").map(str::trim),
+    }
+}
+
+/// Evaluates the string of a `#[doc]` attribute, which can be built with
+/// `include_str!`, `concat!`, `stringify!`, and `env!`, or `None` if it is
+/// built some other way.
+fn evaluate(expr: &Expr) -> syn::Result<Option<String>> {
+    let mac = match ungroup(expr) {
+        Expr::Lit(ExprLit { lit, .. }) => {
+            return Ok(match lit {
+                Lit::Str(string) => Some(string.value()),
+                Lit::Char(c) => Some(c.value().to_string()),
+                Lit::Int(int) => Some(int.base10_digits().to_owned()),
+                Lit::Float(float) => Some(float.base10_digits().to_owned()),
+                Lit::Bool(bool) => Some(bool.value.to_string()),
+                _ => None,
+            })
+        }
+        Expr::Macro(mac) => &mac.mac,
+        _ => return Ok(None),
+    };
+    let Some(name) = mac
+        .path
+        .segments
+        .last()
+        .map(|segment| segment.ident.to_string())
+    else {
+        return Ok(None);
+    };
+
+    if name == "stringify" {
+        return Ok(Some(mac.tokens.to_string()));
+    }
+    let args = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)?;
+    let mut values = Vec::new();
+    for arg in &args {
+        match evaluate(arg)? {
+            Some(value) => values.push(value),
+            None => return Ok(None),
+        }
+    }
+
+    match (name.as_str(), values.as_slice()) {
+        ("concat", values) => Ok(Some(values.concat())),
+        ("env", [variable, ..]) => config::var(variable).map(Some).ok_or_else(|| {
+            syn::Error::new_spanned(mac, format!("environment variable `{variable}` isn't set"))
+        }),
+        ("include_str", [path]) => read_relative(path, mac.path.span(), mac).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// Reads a file whose path is relative to the source file of `span`, like it
+/// is for `include_str!`.
+pub(crate) fn read_relative(path: &str, span: Span, tokens: impl ToTokens) -> syn::Result<String> {
+    let dir = span
+        .unwrap()
+        .local_file()
+        .and_then(|file| file.parent().map(Path::to_path_buf))
+        .or_else(|| std::env::var("CARGO_MANIFEST_DIR").ok().map(PathBuf::from))
+        .unwrap_or_default();
+    let file = dir.join(path);
+    read_file(&file).map_err(|error| {
+        syn::Error::new_spanned(tokens, format!("can't read `{}`: {error}", file.display()))
+    })
+}
+
+thread_local! {
+    /// The files that were read for the documentation that is being
+    /// generated.
+    static READ_FILES: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Reads a file for the documentation, which is then tracked by
+/// [`tracked_files`].
+fn read_file(file: &Path) -> std::io::Result<String> {
+    let text = std::fs::read_to_string(file)?;
+    let file = std::fs::canonicalize(file).unwrap_or_else(|_| file.to_path_buf());
+    READ_FILES.with_borrow_mut(|files| {
+        if !files.contains(&file) {
+            files.push(file);
+        }
+    });
+    Ok(text)
+}
+
+/// Items that include the files that were read for the documentation, since
+/// the compiler only rebuilds the crate when they change if they are
+/// included, and forgets about them for the next expansion.
+pub(crate) fn tracked_files() -> TokenStream {
+    READ_FILES
+        .take()
+        .iter()
+        .map(|file| {
+            let file = file.to_string_lossy();
+            quote!(
+                const _: &str = ::core::include_str!(#file);
+            )
+        })
+        .collect()
+}
+
+/// Splits the text of a file into lines like the ones of doc comments.
+pub(crate) fn text_lines(text: &str, span: Span) -> Vec<Line> {
+    text.lines()
+        .map(|text| Line {
+            text: text.to_owned(),
+            span,
+        })
+        .collect()
+}
+
+/// The lines of a doc comment in one language, from the `# [en]` and `# [de]`
+/// headings that start the text of each language, along with the text of the
+/// other languages.
+///
+/// The language is the given one if there is a heading for it, or else the
+/// first one. The lines before the first heading are shared by every
+/// language, and come after the lines of the language.
+pub(crate) fn select_language(
+    lines: Vec<Line>,
+    language: Option<&str>,
+) -> (Vec<Line>, Vec<(String, String)>) {
+    let mut shared = Vec::new();
+    let mut languages = Vec::<(String, Vec<Line>)>::new();
+    let mut in_fence = false;
+    for line in lines {
+        let trimmed = line.text.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+
+        let heading = trimmed
+            .strip_prefix("# [")
+            .and_then(|rest| rest.strip_suffix(']'))
+            .filter(|name| {
+                !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            });
+        match heading {
+            Some(name) if !in_fence => languages.push((name.to_owned(), Vec::new())),
+            _ => match languages.last_mut() {
+                Some((_, lines)) => lines.push(line),
+                None => shared.push(line),
+            },
+        }
+    }
+    if languages.is_empty() {
+        return (shared, Vec::new());
+    }
+
+    let selected = language
+        .and_then(|language| {
+            languages
+                .iter()
+                .position(|(name, _)| name.eq_ignore_ascii_case(language))
+        })
+        .unwrap_or(0);
+    let (_, mut selected) = languages.remove(selected);
+    if let Some(last) = selected.last().filter(|line| !line.text.trim().is_empty()) {
+        let span = last.span;
+        selected.push(Line {
+            text: String::new(),
+            span,
+        });
+    }
+    selected.extend(shared);
+
+    let others = languages
+        .into_iter()
+        .map(|(name, lines)| (name, join(&lines)))
+        .collect();
+    (selected, others)
+}
+
+/// The note of a `#[deprecated]` attribute, which is empty if it doesn't have
+/// one, or `None` if there is no such attribute.
+pub(crate) fn deprecation(attrs: &[Attribute]) -> syn::Result<Option<String>> {
+    let Some(attr) = attrs.iter().find(|attr| attr.path().is_ident("deprecated")) else {
+        return Ok(None);
+    };
+
+    let mut note = String::new();
+    let mut since = None;
+    match &attr.meta {
+        Meta::Path(_) => {}
+        Meta::NameValue(nvmeta) => {
+            if let Expr::Lit(ExprLit {
+                lit: Lit::Str(string),
+                ..
+            }) = ungroup(&nvmeta.value)
+            {
+                note = string.value();
+            }
+        }
+        Meta::List(_) => attr.parse_nested_meta(|meta| {
+            let value = meta.value()?.parse::<LitStr>()?.value();
+            if meta.path.is_ident("note") {
+                note = value;
+            } else if meta.path.is_ident("since") {
+                since = Some(value);
+            }
+            Ok(())
+        })?,
+    }
+
+    Ok(Some(match since {
+        Some(since) if note.is_empty() => format!("Since {since}."),
+        Some(since) => format!("Since {since}: {note}"),
+        None => note,
+    }))
+}
+
+impl DocComment {
+    pub(crate) fn parse(mut lines: Vec<Line>, jsdoc: bool) -> DocComment {
+        let front_matter = take_front_matter(&mut lines);
+        let mut directives = take_directives(&mut lines);
+        directives.extend(take_comment_directives(&mut lines));
+        // lines starting with `@` are ordinary text unless asked otherwise
+        let tags = if jsdoc {
+            take_tags(&mut lines)
+        } else {
+            Vec::new()
+        };
+
+        // separate summary from description
+        let first_empty_idx = lines
+            .iter()
+            .position(|line| line.text.trim().is_empty())
+            .unwrap_or(lines.len());
+
+        let summary = lines
+            .drain(0..first_empty_idx)
+            .map(|line| line.text)
+            .collect::<Vec<_>>()
+            .join("");
+        let summary = summary.trim().to_owned();
+
+        let mut body = Vec::new();
+        let mut sections = Vec::<Section>::new();
+        let mut in_fence = false;
+
+        for line in lines {
+            let trimmed = line.text.trim();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_fence = !in_fence;
+            }
+
+            if !in_fence {
+                if let Some(name) = trimmed.strip_prefix("# ") {
+                    sections.push(Section {
+                        name: name.trim().to_owned(),
+                        heading: line,
+                        lines: Vec::new(),
+                    });
+                    continue;
+                }
+            }
+
+            match sections.last_mut() {
+                Some(section) => section.lines.push(line),
+                None => body.push(line),
+            }
+        }
+
+        DocComment {
+            front_matter,
+            directives,
+            tags,
+            summary,
+            body,
+            sections,
+        }
+    }
+}
+
+/// Where the summary ends and the description starts.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum SummaryMode {
+    #[default]
+    FirstParagraph,
+    FirstSentence,
+}
+
+impl SummaryMode {
+    pub(crate) fn parse(text: &str) -> Option<SummaryMode> {
+        match text {
+            "first_paragraph" => Some(SummaryMode::FirstParagraph),
+            "first_sentence" => Some(SummaryMode::FirstSentence),
+            _ => None,
+        }
+    }
+}
+
+impl DocComment {
+    /// Moves everything after the first sentence of the summary to the start
+    /// of the body.
+    pub(crate) fn split_first_sentence(&mut self) {
+        let end = self.summary.char_indices().find(|&(index, c)| {
+            // the next sentence has to start with a capital letter, which
+            // skips abbreviations like "e.g."
+            let rest = &self.summary[index + c.len_utf8()..];
+            matches!(c, '.' | '!' | '?')
+                && rest.starts_with(char::is_whitespace)
+                && rest.trim_start().starts_with(char::is_uppercase)
+        });
+        let Some((index, c)) = end else {
+            return;
+        };
+
+        // the body already starts with the blank line after the summary
+        let rest = self.summary.split_off(index + c.len_utf8());
+        self.body.insert(
+            0,
+            Line {
+                text: rest.trim().to_owned(),
+                span: Span::call_site(),
+            },
+        );
+    }
+}
+
+/// Removes a ```` ```yaml ```` code block from the start of the lines, along
+/// with the blank lines after it.
+fn take_front_matter(lines: &mut Vec<Line>) -> Option<FrontMatter> {
+    let start = lines.iter().position(|line| !line.text.trim().is_empty())?;
+    let opening = lines[start].text.trim();
+    let marker = ["```", "~~~"]
+        .into_iter()
+        .find(|marker| opening.starts_with(marker))?;
+    if !matches!(opening[marker.len()..].trim(), "yaml" | "yml") {
+        return None;
+    }
+
+    let indent = lines[start].text.len() - lines[start].text.trim_start().len();
+    let end = start
+        + 1
+        + lines[start + 1..]
+            .iter()
+            .position(|line| line.text.trim() == marker)?;
+
+    let content = lines[start + 1..end]
+        .iter()
+        .map(|line| line.text.get(indent..).unwrap_or(line.text.trim_start()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let span = lines[start].span;
+
+    let blank = lines[end + 1..]
+        .iter()
+        .take_while(|line| line.text.trim().is_empty())
+        .count();
+    lines.drain(..end + 1 + blank);
+
+    Some(FrontMatter { content, span })
+}
+
+/// Removes the `@x-` directive lines that are not in code blocks.
+fn take_directives(lines: &mut Vec<Line>) -> Vec<Directive> {
+    let mut directives = Vec::new();
+    let mut in_fence = false;
+
+    lines.retain(|line| {
+        let trimmed = line.text.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        if in_fence {
+            return true;
+        }
+
+        let Some(directive) = trimmed
+            .strip_prefix('@')
+            .filter(|text| text.starts_with("x-"))
+        else {
+            return true;
+        };
+
+        let (name, value) = directive.split_once(':').unwrap_or((directive, ""));
+        if name.contains(char::is_whitespace) {
+            return true;
+        }
+
+        directives.push(Directive {
+            name: name.to_owned(),
+            value: value.trim().to_owned(),
+            span: line.span,
+        });
+        false
+    });
+
+    directives
+}
+
+/// Removes the lines of JSDoc-style tags that are not in code blocks.
+///
+/// A tag continues onto the following lines until a blank line or another tag.
+fn take_tags(lines: &mut Vec<Line>) -> Vec<Tag> {
+    let mut tags = Vec::<Tag>::new();
+    let mut in_fence = false;
+    let mut in_tag = false;
+
+    lines.retain(|line| {
+        let trimmed = line.text.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            in_tag = false;
+        }
+        if in_fence {
+            return true;
+        }
+
+        let tag = trimmed.strip_prefix('@').and_then(|tag| {
+            let (name, text) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+            TAGS.contains(&name).then_some((name, text))
+        });
+
+        match tag {
+            Some((name, text)) => {
+                tags.push(Tag {
+                    name: name.to_owned(),
+                    text: text.trim().to_owned(),
+                    span: line.span,
+                });
+                in_tag = true;
+                false
+            }
+            // a heading, list item, or another kind of `@` line starts
+            // something else
+            None if in_tag && ends_tag(trimmed) => {
+                in_tag = false;
+                true
+            }
+            None if in_tag && !trimmed.is_empty() => {
+                let tag = tags.last_mut().unwrap();
+                if !tag.text.is_empty() {
+                    tag.text.push(' ');
+                }
+                tag.text.push_str(trimmed);
+                false
+            }
+            None => {
+                in_tag = false;
+                true
+            }
+        }
+    });
+
+    tags
+}
+
+/// Whether a line isn't the continuation of a tag.
+fn ends_tag(trimmed: &str) -> bool {
+    trimmed.is_empty()
+        || trimmed.starts_with('@')
+        || heading_name(trimmed).is_some()
+        || trimmed.starts_with("- ")
+        || trimmed.starts_with("* ")
+}
+
+/// Removes the `<!-- aide: ... -->` comments that are not in code blocks,
+/// which may span several lines.
+fn take_comment_directives(lines: &mut Vec<Line>) -> Vec<Directive> {
+    let mut directives = Vec::new();
+    let mut kept = Vec::new();
+    let mut in_fence = false;
+    let mut open = None::<(String, Span)>;
+
+    for mut line in lines.drain(..) {
+        let trimmed = line.text.trim();
+        if open.is_none() && (trimmed.starts_with("```") || trimmed.starts_with("~~~")) {
+            in_fence = !in_fence;
+        }
+        if in_fence {
+            kept.push(line);
+            continue;
+        }
+
+        // the parts of the line that are outside of comments
+        let mut text = String::new();
+        let mut rest = line.text.as_str();
+        let mut in_comment = open.is_some();
+        loop {
+            if let Some((content, span)) = &mut open {
+                let Some((inside, after)) = rest.split_once("-->") else {
+                    content.push(' ');
+                    content.push_str(rest);
+                    break;
+                };
+                content.push(' ');
+                content.push_str(inside);
+                directives.extend(comment_directives(content, *span));
+                open = None;
+                rest = after;
+            } else {
+                let start = ["<!-- aide:", "<!--aide:"]
+                    .into_iter()
+                    .find_map(|marker| rest.split_once(marker));
+                let Some((before, after)) = start else {
+                    text.push_str(rest);
+                    break;
+                };
+                text.push_str(before);
+                open = Some((String::new(), line.span));
+                in_comment = true;
+                rest = after;
+            }
+        }
+
+        if in_comment {
+            // a line that was only a comment is removed entirely
+            if text.trim().is_empty() {
+                continue;
+            }
+            line.text = text.trim_end().to_owned();
+        }
+        kept.push(line);
+    }
+
+    *lines = kept;
+    directives
+}
+
+/// The `key=value` entries of an `<!-- aide: ... -->` comment, where values
+/// with spaces can be quoted.
+fn comment_directives(content: &str, span: Span) -> Vec<Directive> {
+    let mut directives = Vec::new();
+    let mut rest = content.trim();
+
+    while !rest.is_empty() {
+        let end = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == ';')
+            .unwrap_or(rest.len());
+        let name = &rest[..end];
+        rest = &rest[end..];
+
+        let value = match rest.strip_prefix('=') {
+            Some(after) if after.starts_with('"') => {
+                let end = after[1..].find('"').map_or(after.len(), |end| end + 2);
+                rest = &after[end..];
+                after[1..end].trim_end_matches('"').to_owned()
+            }
+            Some(after) => {
+                let end = after
+                    .find(|c: char| c.is_whitespace() || c == ';')
+                    .unwrap_or(after.len());
+                rest = &after[end..];
+                after[..end].to_owned()
+            }
+            None => String::new(),
+        };
+
+        if !name.is_empty() {
+            directives.push(Directive {
+                name: name.to_owned(),
+                value,
+                span,
+            });
+        }
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ';');
+    }
+
+    directives
+}
+
+impl Section {
+    /// Whether this section has the given name, ignoring case.
+    pub(crate) fn is(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name)
+    }
+
+    /// What follows the name in a heading like `# Since 1.4` or `# Protocol: sse`,
+    /// which is empty if there is nothing, or `None` if this section has another
+    /// name.
+    pub(crate) fn argument(&self, name: &str) -> Option<&str> {
+        let prefix = self.name.get(..name.len())?;
+        let rest = &self.name[name.len()..];
+        if !prefix.eq_ignore_ascii_case(name) || !(rest.is_empty() || rest.starts_with([' ', ':']))
+        {
+            return None;
+        }
+
+        Some(rest.trim_start_matches(':').trim())
+    }
+}
+
+/// A markdown list item, including any indented continuation lines.
+pub(crate) struct Bullet {
+    pub text: String,
+    pub span: Span,
+    lines: Vec<Line>,
+}
+
+/// Goes through the list items in the lines of a section, returning the lines
+/// that were not consumed.
+///
+/// List items start with `- ` or `* ` and may continue onto following lines
+/// as long as they are not blank and don't start a new item. The callback
+/// returns whether it made use of the item.
+pub(crate) fn bullets(
+    lines: &[Line],
+    mut f: impl FnMut(&Bullet) -> syn::Result<bool>,
+) -> syn::Result<Vec<Line>> {
+    let mut rest = Vec::new();
+    let mut current = None::<Bullet>;
+
+    let mut finish = |bullet: Option<Bullet>, rest: &mut Vec<Line>| -> syn::Result<()> {
+        if let Some(bullet) = bullet {
+            if !f(&bullet)? {
+                rest.extend(bullet.lines);
+            }
+        }
+        Ok(())
+    };
+
+    for line in lines {
+        let trimmed = line.text.trim();
+        if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            finish(current.take(), &mut rest)?;
+            current = Some(Bullet {
+                text: item.trim().to_owned(),
+                span: line.span,
+                lines: vec![line.clone()],
+            });
+        } else if let (Some(bullet), false) = (&mut current, trimmed.is_empty()) {
+            bullet.text.push(' ');
+            bullet.text.push_str(trimmed);
+            bullet.lines.push(line.clone());
+        } else {
+            finish(current.take(), &mut rest)?;
+            rest.push(line.clone());
+        }
+    }
+
+    finish(current.take(), &mut rest)?;
+
+    Ok(rest)
+}
+
+/// A fenced code block.
+pub(crate) struct Fence {
+    pub lang: String,
+    pub content: String,
+    pub span: Span,
+    /// The closest non-blank line before the fence, if any.
+    pub label: Option<String>,
+    /// The closest sub-heading before the fence, if any.
+    pub heading: Option<String>,
+    lines: Vec<Line>,
+}
+
+/// Goes through the fenced code blocks in the lines of a section, returning
+/// the lines that were not consumed.
+///
+/// The callback returns whether it made use of the code block.
+pub(crate) fn fences(
+    lines: &[Line],
+    mut f: impl FnMut(&Fence) -> syn::Result<bool>,
+) -> syn::Result<Vec<Line>> {
+    let mut rest = Vec::new();
+    let mut current = None::<(Fence, &str, Vec<&str>)>;
+    let mut label = None;
+    let mut heading = None;
+
+    for line in lines {
+        let trimmed = line.text.trim();
+
+        if let Some((mut fence, marker, content)) = current.take() {
+            fence.lines.push(line.clone());
+            if trimmed == marker {
+                fence.content = content.join("\n");
+                if !f(&fence)? {
+                    rest.extend(fence.lines);
+                }
+                label = None;
+            } else {
+                let indent = fence.lines[0].text.len() - fence.lines[0].text.trim_start().len();
+                let mut content = content;
+                content.push(line.text.get(indent..).unwrap_or(trimmed));
+                current = Some((fence, marker, content));
+            }
+            continue;
+        }
+
+        let marker = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker));
+
+        match marker {
+            Some(marker) => {
+                let fence = Fence {
+                    lang: trimmed[marker.len()..].trim().to_owned(),
+                    content: String::new(),
+                    span: line.span,
+                    label: label.clone(),
+                    heading: heading.clone(),
+                    lines: vec![line.clone()],
+                };
+                current = Some((fence, marker, Vec::new()));
+            }
+            None => {
+                if let Some(name) = heading_name(trimmed) {
+                    heading = Some(name.to_owned());
+                    label = None;
+                } else if !trimmed.is_empty() {
+                    label = Some(trimmed.to_owned());
+                }
+                rest.push(line.clone());
+            }
+        }
+    }
+
+    // an unterminated fence is left as-is
+    if let Some((fence, _, _)) = current {
+        rest.extend(fence.lines);
+    }
+
+    Ok(rest)
+}
+
+/// The text of a markdown heading line, without the leading `#`s.
+pub(crate) fn heading_name(line: &str) -> Option<&str> {
+    let name = line.trim_start_matches('#');
+    if name.len() < line.len() && name.starts_with(' ') {
+        Some(name.trim())
+    } else {
+        None
+    }
+}
+
+/// Splits a `key: value` list item, removing backticks around the key.
+pub(crate) fn key_value(text: &str) -> Option<(&str, &str)> {
+    let (key, value) = text.split_once(':')?;
+    let key = key.trim();
+    let key = key
+        .strip_prefix('`')
+        .and_then(|key| key.strip_suffix('`'))
+        .unwrap_or(key);
+
+    Some((key, value.trim()))
+}
+
+/// The first word of a list item, removing a trailing `:` and backticks, for
+/// items that name something and then describe it.
+pub(crate) fn first_word(text: &str) -> &str {
+    let word = text.split_whitespace().next().unwrap_or_default();
+    let word = word.strip_suffix(':').unwrap_or(word);
+    word.strip_prefix('`')
+        .and_then(|word| word.strip_suffix('`'))
+        .unwrap_or(word)
+}
+
+/// Joins lines back together, trimming the result.
+pub(crate) fn join(lines: &[Line]) -> String {
+    lines
+        .iter()
+        .map(|line| line.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_owned()
+}
@@ -0,0 +1,168 @@
+//! Options that apply to every use of the macro, read from environment
+//! variables at compile time.
+//!
+//! These can be set for a whole project in the `[env]` table of
+//! `.cargo/config.toml`, and the crate is rebuilt when they change.
+
+use std::cell::RefCell;
+use std::path::PathBuf;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// The version of the API the documentation is generated for, from
+/// `AIDECOMMENT_TARGET_VERSION`.
+pub(crate) fn target_version() -> Option<String> {
+    var("AIDECOMMENT_TARGET_VERSION")
+}
+
+/// Whether a `# Panics` section describes the `500` response, from
+/// `AIDECOMMENT_PANICS_AS_500`.
+pub(crate) fn panics_as_500() -> bool {
+    flag("AIDECOMMENT_PANICS_AS_500")
+}
+
+/// Whether operations without a documented id use the name of the handler,
+/// from `AIDECOMMENT_AUTO_OPERATION_ID`.
+pub(crate) fn auto_operation_id() -> bool {
+    flag("AIDECOMMENT_AUTO_OPERATION_ID")
+}
+
+/// Whether JSDoc-style tags are understood in every doc comment, from
+/// `AIDECOMMENT_JSDOC`.
+pub(crate) fn jsdoc() -> bool {
+    flag("AIDECOMMENT_JSDOC")
+}
+
+/// Whether every operation is tagged with the name of the module of its
+/// handler, from `AIDECOMMENT_MODULE_TAGS`.
+pub(crate) fn module_tags() -> bool {
+    flag("AIDECOMMENT_MODULE_TAGS")
+}
+
+/// How the names of modules are written in tags, from
+/// `AIDECOMMENT_MODULE_TAG_CASE`, which is `snake`, `kebab`, `title`, or
+/// `pascal`.
+pub(crate) fn module_tag_case() -> Option<String> {
+    var("AIDECOMMENT_MODULE_TAG_CASE")
+}
+
+/// What the tags named after modules start with, from
+/// `AIDECOMMENT_MODULE_TAG_PREFIX`.
+pub(crate) fn module_tag_prefix() -> Option<String> {
+    var("AIDECOMMENT_MODULE_TAG_PREFIX")
+}
+
+/// The directory that `{{include:name}}` snippets are read from, from
+/// `AIDECOMMENT_SNIPPETS`, which is relative to the crate and is `snippets` by
+/// default.
+pub(crate) fn snippets_dir() -> PathBuf {
+    let snippets = var("AIDECOMMENT_SNIPPETS").unwrap_or_else(|| "snippets".to_owned());
+    std::env::var("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join(snippets)
+}
+
+/// The language of the `# [en]` sections that are documented, from
+/// `AIDECOMMENT_LANGUAGE`.
+pub(crate) fn language() -> Option<String> {
+    var("AIDECOMMENT_LANGUAGE")
+}
+
+/// Whether the other languages are kept in the `x-descriptions` extension,
+/// from `AIDECOMMENT_X_DESCRIPTIONS`.
+pub(crate) fn x_descriptions() -> bool {
+    flag("AIDECOMMENT_X_DESCRIPTIONS")
+}
+
+/// Where the summary ends, from `AIDECOMMENT_SUMMARY_MODE`, which is
+/// `first_paragraph` or `first_sentence`.
+pub(crate) fn summary_mode() -> Option<String> {
+    var("AIDECOMMENT_SUMMARY_MODE")
+}
+
+/// The sections that are left out of the description, from a comma-separated
+/// `AIDECOMMENT_STRIP_SECTIONS`.
+///
+/// By default these are the rustdoc sections that only make sense for Rust
+/// callers.
+pub(crate) fn stripped_sections() -> Vec<String> {
+    match var("AIDECOMMENT_STRIP_SECTIONS") {
+        Some(sections) => sections
+            .split(',')
+            .map(str::trim)
+            .filter(|section| !section.is_empty())
+            .map(str::to_owned)
+            .collect(),
+        None => vec!["Safety".to_owned(), "Panics".to_owned()],
+    }
+}
+
+/// The standard parameters that a `# Pagination` section adds, from
+/// `AIDECOMMENT_PAGINATION`, which is `page`, `offset`, or `cursor`.
+pub(crate) fn pagination_profile() -> String {
+    var("AIDECOMMENT_PAGINATION").unwrap_or_else(|| "page".to_owned())
+}
+
+/// The audiences that the document is generated for, from a comma-separated
+/// `AIDECOMMENT_AUDIENCE`.
+pub(crate) fn audience() -> Option<Vec<String>> {
+    let audiences = var("AIDECOMMENT_AUDIENCE")?;
+    Some(
+        audiences
+            .split(',')
+            .map(str::trim)
+            .filter(|audience| !audience.is_empty())
+            .map(str::to_owned)
+            .collect(),
+    )
+}
+
+/// The path that a crate is used through, from `AIDECOMMENT_AIDE_PATH` or
+/// `AIDECOMMENT_AXUM_PATH`, along with the name of the variable.
+pub(crate) fn crate_path(name: &str) -> Option<(String, String)> {
+    let variable = format!("AIDECOMMENT_{}_PATH", name.to_uppercase());
+    let path = var(&variable)?;
+    Some((variable, path))
+}
+
+fn flag(name: &str) -> bool {
+    match var(name) {
+        Some(value) => !matches!(value.trim(), "" | "0" | "false" | "no" | "off"),
+        None => false,
+    }
+}
+
+thread_local! {
+    /// The environment variables that were read for the documentation that is
+    /// being generated.
+    static READ_VARIABLES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Reads an environment variable for the documentation, which is then
+/// tracked by [`tracked_variables`].
+pub(crate) fn var(name: &str) -> Option<String> {
+    READ_VARIABLES.with_borrow_mut(|variables| {
+        if !variables.iter().any(|variable| variable == name) {
+            variables.push(name.to_owned());
+        }
+    });
+    std::env::var(name).ok()
+}
+
+/// Items that read the environment variables that were read for the
+/// documentation, since the compiler only rebuilds the crate when they change
+/// if they are read with `option_env!`, and forgets about them for the next
+/// expansion.
+pub(crate) fn tracked_variables() -> TokenStream {
+    READ_VARIABLES
+        .take()
+        .iter()
+        .map(|variable| {
+            quote!(
+                const _: ::core::option::Option<&str> = ::core::option_env!(#variable);
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,137 @@
+//! The `ErrorResponses` derive, which documents the responses of an error
+//! type for the `error` argument of the attribute, and the
+//! `AideCommentResponses` derive, which documents them as its
+//! `OperationOutput`.
+
+use std::collections::BTreeMap;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Expr, ExprLit, Lit};
+
+use crate::args::Args;
+use crate::comment;
+
+/// Generates the `AIDECOMMENT_RESPONSES` constant of the type, which lists
+/// the statuses of its variants along with their descriptions.
+pub(crate) fn derive(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let responses = responses(input, "ErrorResponses")?
+        .into_iter()
+        .map(|(code, description)| quote! { (#code, #description) });
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #[doc(hidden)]
+            pub const AIDECOMMENT_RESPONSES: &'static [(u16, &'static str)] = &[#(#responses),*];
+        }
+    })
+}
+
+/// Generates an `OperationOutput` implementation for the type, whose inferred
+/// responses are the statuses of its variants along with their descriptions.
+pub(crate) fn derive_output(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let aide = Args::default().crate_path("aide")?;
+    let responses =
+        responses(input, "AideCommentResponses")?
+            .into_iter()
+            .map(|(code, description)| {
+                quote! {
+                    (
+                        ::core::option::Option::Some(#code),
+                        __aide::openapi::Response {
+                            description: #description.to_owned(),
+                            ..::core::default::Default::default()
+                        },
+                    )
+                }
+            });
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        const _: () = {
+            use #aide as __aide;
+
+            impl #impl_generics __aide::OperationOutput for #ident #ty_generics #where_clause {
+                type Inner = ();
+
+                fn inferred_responses(
+                    _ctx: &mut __aide::gen::GenContext,
+                    _operation: &mut __aide::openapi::Operation,
+                ) -> Vec<(Option<u16>, __aide::openapi::Response)> {
+                    vec![#(#responses),*]
+                }
+            }
+        };
+    })
+}
+
+/// The statuses of the variants of an enum along with their descriptions,
+/// for the derive with the given name.
+fn responses(input: &DeriveInput, derive: &str) -> syn::Result<Vec<(u16, String)>> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            format!("`{derive}` can only be derived for enums"),
+        ));
+    };
+
+    // variants with the same status are listed in one response
+    let mut responses = BTreeMap::<u16, Vec<String>>::new();
+    for variant in &data.variants {
+        let Some(attr) = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("status"))
+        else {
+            return Err(syn::Error::new_spanned(
+                &variant.ident,
+                "missing a `#[status(...)]` attribute",
+            ));
+        };
+
+        let code = match comment::ungroup(&attr.parse_args::<Expr>()?) {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(code),
+                ..
+            }) => code.base10_parse::<u16>()?,
+            expr => return Err(syn::Error::new_spanned(expr, "expected a status code")),
+        };
+        if !(100..=599).contains(&code) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!("invalid status `{code}`"),
+            ));
+        }
+
+        let description = comment::doc_lines(&variant.attrs)?
+            .iter()
+            .map(|line| line.text.trim())
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_owned();
+        responses.entry(code).or_default().push(description);
+    }
+
+    let responses = responses
+        .into_iter()
+        .map(|(code, descriptions)| {
+            let description = match descriptions.as_slice() {
+                [description] => description.clone(),
+                _ => descriptions
+                    .iter()
+                    .map(|description| format!("- {description}"))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            };
+            (code, description)
+        })
+        .collect();
+
+    Ok(responses)
+}
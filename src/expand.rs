@@ -0,0 +1,770 @@
+//! Generating the code that applies an [`OperationDoc`] to an aide
+//! `Operation`.
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::Type;
+
+use crate::operation::{
+    Case, ExampleTarget, Location, Merge, OperationDoc, ParameterDoc, SchemaType, Status,
+    UploadFieldDoc,
+};
+
+/// Generates the body of `OperationInput::operation_input`, which has
+/// `_ctx`, `operation`, and the [`pending_responses`] in scope.
+pub(crate) fn operation_input(doc: &OperationDoc) -> TokenStream {
+    let summary = &doc.summary;
+    let description = match doc.deprecated.as_deref() {
+        Some(note) if !note.is_empty() => format!("**Deprecated:** {note}\n\n{}", doc.description)
+            .trim()
+            .to_owned(),
+        _ => doc.description.clone(),
+    };
+    let deprecated = doc.deprecated.is_some().then(|| {
+        quote! { operation.deprecated = true; }
+    });
+    let tags = &doc.tags;
+    let module_tag = doc.module_tag.as_ref().map(module_tag);
+    let operation_id = doc.operation_id.as_ref().map(|operation_id| {
+        quote! { operation.operation_id = Some(#operation_id.to_owned()); }
+    });
+    let responses = responses(doc);
+    let parameters = parameters(&doc.parameters);
+    let request_body = request_body(doc);
+    let upload_fields = upload_fields(&doc.upload_fields);
+    let examples = examples(doc);
+    let media_types = media_types(doc);
+    let security = security(doc);
+    let external_docs = external_docs(doc);
+    let servers = servers(doc);
+    let callbacks = callbacks(doc);
+    let extensions = extensions(doc);
+
+    let summary =
+        (!doc.omit_summary).then(|| merge(doc.merge, quote! { operation.summary }, summary, " "));
+    let description = (!doc.omit_description).then(|| {
+        merge(
+            doc.merge,
+            quote! { operation.description },
+            &description,
+            "\n\n",
+        )
+    });
+
+    quote! {
+        #summary
+        #description
+        #deprecated
+        operation.tags.extend([#(#tags.to_owned()),*]);
+        #module_tag
+        #operation_id
+        #responses
+        #parameters
+        #request_body
+        #upload_fields
+        #examples
+        #media_types
+        #security
+        #external_docs
+        #servers
+        #callbacks
+        #extensions
+    }
+}
+
+/// Generates the tag named after the module of the handler, which is only
+/// known where the code is generated, so it is converted to the case there.
+///
+/// Handlers at the root of the crate aren't in a module, so they aren't
+/// tagged.
+fn module_tag((case, prefix): &(Case, String)) -> TokenStream {
+    let name = match case {
+        Case::Snake => quote! { module.to_owned() },
+        Case::Kebab => quote! { module.replace('_', "-") },
+        Case::Title | Case::Pascal => {
+            let separator = if *case == Case::Title { " " } else { "" };
+            quote! {
+                module
+                    .split('_')
+                    .filter(|word| !word.is_empty())
+                    .map(|word| {
+                        let mut chars = word.chars();
+                        chars.next().into_iter().flat_map(char::to_uppercase).chain(chars).collect::<String>()
+                    })
+                    .collect::<Vec<_>>()
+                    .join(#separator)
+            }
+        }
+    };
+
+    quote! {
+        if let Some((_, module)) = ::core::module_path!().rsplit_once("::") {
+            let tag = format!("{}{}", #prefix, #name);
+            if !operation.tags.contains(&tag) {
+                operation.tags.push(tag);
+            }
+        }
+    }
+}
+
+/// Generates the assignment of `text` to an `Option<String>` field, which is
+/// joined to an existing value with the separator when appending.
+fn merge(merge: Merge, field: TokenStream, text: &str, separator: &str) -> TokenStream {
+    match merge {
+        Merge::Replace => quote! {
+            #field = Some(#text.to_owned());
+        },
+        Merge::Append => quote! {
+            #field = Some(match #field.take().filter(|existing| !existing.is_empty()) {
+                Some(existing) if !#text.is_empty() => format!("{}{}{}", existing, #separator, #text),
+                Some(existing) => existing,
+                None => #text.to_owned(),
+            });
+        },
+        Merge::Fill => quote! {
+            if #field.as_deref().map_or(true, str::is_empty) {
+                #field = Some(#text.to_owned());
+            }
+        },
+    }
+}
+
+fn responses(doc: &OperationDoc) -> TokenStream {
+    let statuses = doc.documented_statuses();
+    if statuses.is_empty() && !doc.documents_success() {
+        return TokenStream::new();
+    }
+
+    let returns = doc.returns.as_ref().map(|description| {
+        let entry = success_response_entry();
+
+        quote! {
+            if let Some(__aide::openapi::ReferenceOr::Item(response)) = #entry {
+                response.description = #description.to_owned();
+            }
+        }
+    });
+
+    let responses = doc.responses.iter().map(|response| {
+        let entry = response_entry(response.status);
+        let description = &response.description;
+
+        quote! {
+            if let Some(__aide::openapi::ReferenceOr::Item(response)) = #entry {
+                response.description = #description.to_owned();
+            }
+        }
+    });
+
+    let headers = doc.response_headers.iter().map(|header| {
+        let entry = match header.status {
+            Some(status) => response_entry(status),
+            None => success_response_entry(),
+        };
+        let name = &header.name;
+        let description = &header.description;
+        let required = header.required.unwrap_or(false);
+        let example = match &header.example {
+            Some(example) => {
+                let example = example.to_string();
+                quote! { Some(#example.parse().unwrap()) }
+            }
+            None => quote! { None },
+        };
+
+        quote! {
+            if let Some(__aide::openapi::ReferenceOr::Item(response)) = #entry {
+                response.headers.insert(#name.to_owned(), __aide::openapi::ReferenceOr::Item(__aide::openapi::Header {
+                    description: Some(#description.to_owned()),
+                    style: ::core::default::Default::default(),
+                    required: #required,
+                    deprecated: None,
+                    format: __aide::openapi::ParameterSchemaOrContent::Schema(__aide::openapi::SchemaObject {
+                        json_schema: _ctx.schema.subschema_for::<::std::string::String>(),
+                        external_docs: None,
+                        example: None,
+                    }),
+                    example: #example,
+                    examples: ::core::default::Default::default(),
+                    extensions: ::core::default::Default::default(),
+                }));
+            }
+        }
+    });
+
+    quote! {
+        let responses = operation.responses.get_or_insert_with(::core::default::Default::default);
+        #returns
+        #(#responses)*
+        #(#headers)*
+    }
+}
+
+fn parameters(parameters: &[ParameterDoc]) -> TokenStream {
+    parameters
+        .iter()
+        .map(|parameter| {
+            let name = &parameter.name;
+            let description = &parameter.description;
+            let position = parameter_position(name, parameter.location);
+
+            let required = parameter.required.map(|required| {
+                quote! { parameter.required = #required; }
+            });
+
+            let missing = match parameter.location {
+                Some(location) if parameter.add_if_missing => {
+                    let required = parameter.required.unwrap_or(false);
+                    let new = new_parameter(location, name, description, required, parameter.schema_type);
+                    quote! {
+                        operation.parameters.push(__aide::openapi::ReferenceOr::Item(#new));
+                        Some(operation.parameters.len() - 1)
+                    }
+                }
+                _ => quote! {
+                    _ctx.error(__aide::Error::ParameterNotExists(#name.to_owned()));
+                    None
+                },
+            };
+
+            let schema = parameter_schema(parameter);
+            let example = parameter.example.as_ref().map(|example| {
+                let example = example.to_string();
+                quote! { parameter.example = Some(#example.parse().unwrap()); }
+            });
+
+            quote! {
+                let position = match #position {
+                    Some(position) => Some(position),
+                    None => { #missing }
+                };
+                if let Some(__aide::openapi::ReferenceOr::Item(parameter)) = position.and_then(|position| operation.parameters.get_mut(position)) {
+                    let parameter = parameter.parameter_data_mut();
+                    parameter.description = Some(#description.to_owned());
+                    #required
+                    #example
+                    #schema
+                }
+            }
+        })
+        .collect()
+}
+
+/// Generates the changes to the schema of `parameter`, if there are any.
+fn parameter_schema(parameter: &ParameterDoc) -> TokenStream {
+    let mut changes = Vec::new();
+
+    if !parameter.enum_values.is_empty() {
+        let enum_values = parameter.enum_values.iter().map(|value| value.to_string());
+        changes.push(quote! {
+            object.enum_values = Some(vec![#(#enum_values.parse().unwrap()),*]);
+        });
+    }
+    if let Some(minimum) = parameter.minimum {
+        changes.push(quote! { object.number().minimum = Some(#minimum); });
+    }
+    if let Some(maximum) = parameter.maximum {
+        changes.push(quote! { object.number().maximum = Some(#maximum); });
+    }
+    if let Some(pattern) = &parameter.pattern {
+        changes.push(quote! { object.string().pattern = Some(#pattern.to_owned()); });
+    }
+
+    if changes.is_empty() {
+        return TokenStream::new();
+    }
+
+    quote! {
+        if let __aide::openapi::ParameterSchemaOrContent::Schema(schema) = &mut parameter.format {
+            let mut object = schema.json_schema.clone().into_object();
+            #(#changes)*
+            schema.json_schema = object.into();
+        }
+    }
+}
+
+fn request_body(doc: &OperationDoc) -> TokenStream {
+    let Some(description) = &doc.request_body else {
+        return TokenStream::new();
+    };
+
+    let entry = request_body_entry();
+
+    quote! {
+        if let __aide::openapi::ReferenceOr::Item(request_body) = #entry {
+            request_body.description = Some(#description.to_owned());
+        }
+    }
+}
+
+/// Generates the properties of the `multipart/form-data` schema of the
+/// request body for the documented fields.
+fn upload_fields(fields: &[UploadFieldDoc]) -> TokenStream {
+    if fields.is_empty() {
+        return TokenStream::new();
+    }
+
+    let fields = fields.iter().map(|field| {
+        let name = &field.name;
+        let description = &field.description;
+        let ty = rust_type(field.schema_type);
+        let format = (field.schema_type == SchemaType::Binary).then(|| {
+            quote! { property.format = Some("binary".to_owned()); }
+        });
+        let required = field.required.then(|| {
+            quote! { object.object().required.insert(#name.to_owned()); }
+        });
+
+        quote! {
+            {
+                let schema = object
+                    .object()
+                    .properties
+                    .entry(#name.to_owned())
+                    .or_insert_with(|| _ctx.schema.subschema_for::<#ty>());
+                let mut property = schema.clone().into_object();
+                if !#description.is_empty() {
+                    property.metadata().description = Some(#description.to_owned());
+                }
+                #format
+                *schema = property.into();
+            }
+            #required
+        }
+    });
+
+    let entry = request_body_entry();
+
+    quote! {
+        if let __aide::openapi::ReferenceOr::Item(request_body) = #entry {
+            // aide describes multipart bodies as arrays, but the fields are the
+            // properties of an object, which is the type of a map's schema
+            let object_schema = _ctx.schema.subschema_for::<::std::collections::BTreeMap<::std::string::String, ::std::string::String>>();
+            let media_type = request_body.content.entry("multipart/form-data".to_owned()).or_default();
+            let schema = media_type.schema.get_or_insert_with(|| __aide::openapi::SchemaObject {
+                json_schema: object_schema.clone(),
+                external_docs: None,
+                example: None,
+            });
+            let mut object = schema.json_schema.clone().into_object();
+            object.instance_type = object_schema.into_object().instance_type;
+            object.object().additional_properties = None;
+            #(#fields)*
+            schema.json_schema = object.into();
+        }
+    }
+}
+
+fn examples(doc: &OperationDoc) -> TokenStream {
+    doc.examples
+        .iter()
+        .map(|example| {
+            let value = &example.value;
+            let media_type = example.media_type;
+            let set = match &example.name {
+                Some(name) => quote! {
+                    .examples.insert(#name.to_owned(), __aide::openapi::ReferenceOr::Item(__aide::openapi::Example {
+                        summary: Some(#name.to_owned()),
+                        value: example,
+                        ..::core::default::Default::default()
+                    }))
+                },
+                None => quote! { .example = example },
+            };
+
+            let request_body_entry = request_body_entry();
+            let request_body = quote! {
+                if let __aide::openapi::ReferenceOr::Item(request_body) = #request_body_entry {
+                    request_body.content.entry(#media_type.to_owned()).or_default()#set;
+                }
+            };
+
+            let success_entry = success_response_entry();
+            let response = quote! {
+                let responses = operation.responses.get_or_insert_with(::core::default::Default::default);
+                if let Some(__aide::openapi::ReferenceOr::Item(response)) = #success_entry {
+                    response.content.entry(#media_type.to_owned()).or_default()#set;
+                }
+            };
+
+            let attach = match example.target {
+                ExampleTarget::Auto => quote! {
+                    if operation.request_body.is_some() {
+                        #request_body
+                    } else {
+                        #response
+                    }
+                },
+                ExampleTarget::RequestBody => request_body,
+                ExampleTarget::Response => response,
+                ExampleTarget::Status(status) => {
+                    let entry = response_entry(status);
+                    quote! {
+                        let responses = operation.responses.get_or_insert_with(::core::default::Default::default);
+                        if let Some(__aide::openapi::ReferenceOr::Item(response)) = #entry {
+                            response.content.entry(#media_type.to_owned()).or_default()#set;
+                        }
+                    }
+                }
+            };
+
+            quote! {
+                {
+                    let example = Some(#value.parse().unwrap());
+                    #attach
+                }
+            }
+        })
+        .collect()
+}
+
+/// Generates the replacement of the inferred media types of the request body
+/// and the successful responses, which keep the schema and examples of the
+/// first one.
+fn media_types(doc: &OperationDoc) -> TokenStream {
+    let replace = |media_types: &[String]| {
+        quote! {
+            let media_type = ::std::mem::take(&mut content)
+                .into_iter()
+                .next()
+                .map(|(_, media_type)| media_type)
+                .unwrap_or_default();
+            content = [#(#media_types),*]
+                .into_iter()
+                .map(|name| (name.to_owned(), media_type.clone()))
+                .collect();
+        }
+    };
+
+    let consumes = (!doc.consumes.is_empty()).then(|| {
+        let entry = request_body_entry();
+        let replace = replace(&doc.consumes);
+
+        quote! {
+            if let __aide::openapi::ReferenceOr::Item(request_body) = #entry {
+                let mut content = ::std::mem::take(&mut request_body.content);
+                #replace
+                request_body.content = content;
+            }
+        }
+    });
+
+    let produces = (!doc.produces.is_empty()).then(|| {
+        let entry = success_response_entry();
+        let replace = replace(&doc.produces);
+
+        quote! {
+            let responses = operation.responses.get_or_insert_with(::core::default::Default::default);
+            let _ = #entry;
+            for (status, response) in &mut responses.responses {
+                // a `204` response has no content to replace
+                let is_success = matches!(
+                    status,
+                    __aide::openapi::StatusCode::Code(200..=203 | 205..=299) | __aide::openapi::StatusCode::Range(2)
+                );
+                if let (true, __aide::openapi::ReferenceOr::Item(response)) = (is_success, response) {
+                    let mut content = ::std::mem::take(&mut response.content);
+                    #replace
+                    response.content = content;
+                }
+            }
+        }
+    });
+
+    quote! {
+        #consumes
+        #produces
+    }
+}
+
+fn security(doc: &OperationDoc) -> TokenStream {
+    let requirements = doc.security.iter().map(|requirement| {
+        let schemes = requirement.iter().map(|(scheme, scopes)| {
+            quote! { (#scheme.to_owned(), vec![#(#scopes.to_owned()),*]) }
+        });
+
+        quote! {
+            operation.security.push([#(#schemes),*].into_iter().collect());
+        }
+    });
+
+    // the scopes also apply to requirements that were added by extractors
+    let scopes = (!doc.scopes.is_empty()).then(|| {
+        let scopes = &doc.scopes;
+
+        quote! {
+            for requirement in &mut operation.security {
+                for scopes in requirement.values_mut() {
+                    scopes.extend([#(#scopes.to_owned()),*]);
+                }
+            }
+        }
+    });
+
+    quote! {
+        #(#requirements)*
+        #scopes
+    }
+}
+
+fn external_docs(doc: &OperationDoc) -> TokenStream {
+    let Some((url, description)) = &doc.external_docs else {
+        return TokenStream::new();
+    };
+
+    let description = match description {
+        Some(description) => quote! { Some(#description.to_owned()) },
+        None => quote! { None },
+    };
+
+    quote! {
+        operation.external_docs = Some(__aide::openapi::ExternalDocumentation {
+            description: #description,
+            url: #url.to_owned(),
+            extensions: ::core::default::Default::default(),
+        });
+    }
+}
+
+fn servers(doc: &OperationDoc) -> TokenStream {
+    doc.servers
+        .iter()
+        .map(|(url, description)| {
+            let description = match description {
+                Some(description) => quote! { Some(#description.to_owned()) },
+                None => quote! { None },
+            };
+
+            quote! {
+                operation.servers.push(__aide::openapi::Server {
+                    url: #url.to_owned(),
+                    description: #description,
+                    ..::core::default::Default::default()
+                });
+            }
+        })
+        .collect()
+}
+
+fn callbacks(doc: &OperationDoc) -> TokenStream {
+    let new = new_item();
+
+    doc.callbacks
+        .iter()
+        .map(|callback| {
+            let name = &callback.name;
+            let url = &callback.url;
+            let method = Ident::new(&callback.method, Span::call_site());
+            let description = &callback.description;
+
+            quote! {
+                if let __aide::openapi::ReferenceOr::Item(callback) = operation.callbacks.entry(#name.to_owned()).or_insert_with(#new) {
+                    if let __aide::openapi::ReferenceOr::Item(path) = callback.entry(#url.to_owned()).or_insert_with(#new) {
+                        path.#method = Some(__aide::openapi::Operation {
+                            description: Some(#description.to_owned()),
+                            ..::core::default::Default::default()
+                        });
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+fn extensions(doc: &OperationDoc) -> TokenStream {
+    doc.extensions
+        .iter()
+        .map(|(key, value)| {
+            let value = value.to_string();
+
+            quote! {
+                operation.extensions.insert(#key.to_owned(), #value.parse().unwrap());
+            }
+        })
+        .collect()
+}
+
+/// An expression for the position of the parameter with the given name (and
+/// location if given) in `operation.parameters`, if it exists.
+fn parameter_position(name: &str, location: Option<Location>) -> TokenStream {
+    let pattern = match location {
+        Some(location) => {
+            let variant = location_variant(location);
+            quote! { parameter @ __aide::openapi::Parameter::#variant { .. } }
+        }
+        None => quote! { parameter },
+    };
+
+    // header names are case-insensitive
+    let matches = match location {
+        Some(Location::Header) => quote! { data.name.eq_ignore_ascii_case(#name) },
+        _ => quote! { data.name == #name },
+    };
+
+    quote! {
+        operation
+            .parameters
+            .iter()
+            .position(|parameter| match parameter {
+                __aide::openapi::ReferenceOr::Item(#pattern) => {
+                    let data = parameter.parameter_data_ref();
+                    #matches
+                }
+                _ => false,
+            })
+    }
+}
+
+/// An expression for a new parameter.
+fn new_parameter(
+    location: Location,
+    name: &str,
+    description: &str,
+    required: bool,
+    schema_type: SchemaType,
+) -> TokenStream {
+    let variant = location_variant(location);
+    let ty = rust_type(schema_type);
+    let query_fields = (location == Location::Query).then(|| {
+        quote! {
+            allow_reserved: false,
+            allow_empty_value: None,
+        }
+    });
+
+    quote! {
+        __aide::openapi::Parameter::#variant {
+            parameter_data: __aide::openapi::ParameterData {
+                name: #name.to_owned(),
+                description: Some(#description.to_owned()),
+                required: #required,
+                deprecated: None,
+                format: __aide::openapi::ParameterSchemaOrContent::Schema(__aide::openapi::SchemaObject {
+                    json_schema: _ctx.schema.subschema_for::<#ty>(),
+                    external_docs: None,
+                    example: None,
+                }),
+                example: None,
+                examples: ::core::default::Default::default(),
+                explode: None,
+                extensions: ::core::default::Default::default(),
+            },
+            style: ::core::default::Default::default(),
+            #query_fields
+        }
+    }
+}
+
+/// The Rust type whose schema has the given type.
+fn rust_type(schema_type: SchemaType) -> TokenStream {
+    match schema_type {
+        SchemaType::String | SchemaType::Binary => quote! { ::std::string::String },
+        SchemaType::Integer => quote! { u64 },
+        SchemaType::Number => quote! { f64 },
+        SchemaType::Boolean => quote! { bool },
+    }
+}
+
+fn location_variant(location: Location) -> Ident {
+    let variant = match location {
+        Location::Query => "Query",
+        Location::Path => "Path",
+        Location::Header => "Header",
+        Location::Cookie => "Cookie",
+    };
+
+    Ident::new(variant, Span::call_site())
+}
+
+/// Generates `pending_responses`, the statuses that aide is going to infer
+/// from the `output` of the handler once the operation input is applied.
+///
+/// aide reports a conflict for a response that it infers when one is already
+/// there, so those are only documented by a transform, which is applied after
+/// they are inferred.
+pub(crate) fn pending_responses(output: Option<&Type>) -> TokenStream {
+    match output {
+        Some(output) => quote! {
+            let pending_responses = <#output as __aide::OperationOutput>::inferred_responses(_ctx, operation)
+                .into_iter()
+                .map(|(status, _)| status)
+                .filter(|status| {
+                    let responses = operation.responses.as_ref();
+                    match status {
+                        Some(code) => !responses.map_or(false, |responses| {
+                            responses.responses.contains_key(&__aide::openapi::StatusCode::Code(*code))
+                        }),
+                        None => responses.map_or(true, |responses| responses.default.is_none()),
+                    }
+                })
+                .collect::<::std::vec::Vec<_>>();
+        },
+        None => quote! {
+            let pending_responses = ::std::vec::Vec::<Option<u16>>::new();
+        },
+    }
+}
+
+/// An expression for the response with the given status in `responses`,
+/// inserting an empty one if it doesn't exist yet, or `None` if it is one of
+/// the `pending_responses`.
+fn response_entry(status: Status) -> TokenStream {
+    let new = new_item();
+
+    match status {
+        Status::Default => quote! {
+            if pending_responses.contains(&None) {
+                None
+            } else {
+                Some(responses.default.get_or_insert_with(#new))
+            }
+        },
+        Status::Code(code) => quote! {
+            if pending_responses.contains(&Some(#code)) {
+                None
+            } else {
+                Some(responses.responses.entry(__aide::openapi::StatusCode::Code(#code)).or_insert_with(#new))
+            }
+        },
+        Status::Range(class) => quote! {
+            Some(responses.responses.entry(__aide::openapi::StatusCode::Range(#class)).or_insert_with(#new))
+        },
+    }
+}
+
+/// An expression for the successful response in `responses`, which is the
+/// first `2XX` response or `200` if there isn't one, inserting an empty one if
+/// it doesn't exist yet, or `None` if aide is going to infer it.
+fn success_response_entry() -> TokenStream {
+    let new = new_item();
+
+    quote! {
+        {
+            let status = responses
+                .responses
+                .keys()
+                .find(|status| matches!(status, __aide::openapi::StatusCode::Code(200..=299)))
+                .cloned();
+            match status {
+                Some(status) => Some(responses.responses.entry(status).or_insert_with(#new)),
+                None if pending_responses.iter().any(|status| matches!(status, Some(200..=299))) => None,
+                None => Some(responses.responses.entry(__aide::openapi::StatusCode::Code(200)).or_insert_with(#new)),
+            }
+        }
+    }
+}
+
+/// An expression for the request body, inserting an empty one if it doesn't
+/// exist yet.
+fn request_body_entry() -> TokenStream {
+    let new = new_item();
+
+    quote! {
+        operation.request_body.get_or_insert_with(#new)
+    }
+}
+
+/// A closure creating an empty `ReferenceOr::Item`.
+fn new_item() -> TokenStream {
+    quote! { || __aide::openapi::ReferenceOr::Item(::core::default::Default::default()) }
+}
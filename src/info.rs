@@ -0,0 +1,78 @@
+//! The `aidecomment_info!` macro, which describes the document with the doc
+//! comment of the crate and its Cargo metadata.
+
+use std::path::PathBuf;
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::LitStr;
+
+use crate::args::Args;
+use crate::tag;
+
+/// Generates an aide `Info` from the doc comment of the crate root at the
+/// given path, or the `src/lib.rs` or `src/main.rs` of the package.
+pub(crate) fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let path = if input.is_empty() {
+        None
+    } else {
+        Some(syn::parse2::<LitStr>(input)?)
+    };
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .map_err(|_| syn::Error::new(Span::call_site(), "`CARGO_MANIFEST_DIR` isn't set"))?;
+    let root = match &path {
+        Some(path) => manifest_dir.join(path.value()),
+        None => ["src/lib.rs", "src/main.rs"]
+            .iter()
+            .map(|path| manifest_dir.join(path))
+            .find(|path| path.exists())
+            .ok_or_else(|| {
+                syn::Error::new(
+                    Span::call_site(),
+                    "there is no `src/lib.rs` or `src/main.rs`, so the crate root needs to be given, like `aidecomment_info!(\"src/bin/server.rs\")`",
+                )
+            })?,
+    };
+    let span = path.as_ref().map_or_else(Span::call_site, LitStr::span);
+    let source = std::fs::read_to_string(&root).map_err(|error| {
+        syn::Error::new(span, format!("can't read `{}`: {error}", root.display()))
+    })?;
+    let file = syn::parse_file(&source)?;
+
+    // the first paragraph is the title, which may be written as a heading
+    let doc = tag::description(&file.attrs)?;
+    let (title, description) = match doc.split_once("\n\n") {
+        Some((title, description)) => (title, description.trim()),
+        None => (doc.as_str(), ""),
+    };
+    let title = title.trim_start_matches('#').trim().replace('\n', " ");
+    let title = if title.is_empty() {
+        quote!(::core::env!("CARGO_PKG_NAME").to_owned())
+    } else {
+        quote!(#title.to_owned())
+    };
+    let description = if description.is_empty() {
+        quote!(Some(::core::env!("CARGO_PKG_DESCRIPTION").to_owned())
+            .filter(|description| !description.is_empty()))
+    } else {
+        quote!(Some(#description.to_owned()))
+    };
+
+    let aide = Args::default().crate_path("aide")?;
+    let root = root.display().to_string();
+    Ok(quote! {
+        {
+            // the document is generated again when the crate root changes
+            const _: &str = ::core::include_str!(#root);
+
+            #aide::openapi::Info {
+                title: #title,
+                description: #description,
+                version: ::core::env!("CARGO_PKG_VERSION").to_owned(),
+                ..::core::default::Default::default()
+            }
+        }
+    })
+}
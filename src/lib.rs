@@ -3,10 +3,34 @@
 //! [axum](https://crates.io/crates/axum) and integrates this information with
 //! [aide](https://crates.io/crates/aide).
 
+mod args;
+mod comment;
+mod config;
+mod errors;
+mod expand;
+mod info;
+mod middleware;
+mod operation;
+mod params;
+mod response;
+mod routes;
+mod security;
+mod tag;
+mod yaml;
+
 use proc_macro::TokenStream;
-use proc_macro2::Span;
-use quote::quote;
-use syn::{Expr, FnArg, Ident, ItemFn, Lit};
+use proc_macro2::{Group, Span, TokenTree};
+use quote::{quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::parse::{ParseStream, Parser};
+use syn::{
+    parse_quote, Attribute, DeriveInput, ExprClosure, FnArg, GenericParam, Ident, ImplItem, Item,
+    ItemImpl, ItemMod, ItemTrait, Meta, Pat, ReturnType, Signature, TraitItem, Type, Visibility,
+};
+
+use crate::args::Args;
+use crate::comment::{DocComment, SummaryMode};
+use crate::operation::OperationDoc;
 
 /// An attribute to provide the summary and description from a doc comment.
 ///
@@ -15,6 +39,10 @@ use syn::{Expr, FnArg, Ident, ItemFn, Lit};
 /// paragraph is interpretted as the summary and everything else is interpretted
 /// as the description.
 ///
+/// The last argument of the handler is wrapped so that the documentation is
+/// applied after the other extractors have contributed to the operation. A
+/// handler without arguments gets one added.
+///
 /// ```
 /// # use aidecomment::aidecomment;
 /// /// This is a summary
@@ -25,84 +53,2015 @@ use syn::{Expr, FnArg, Ident, ItemFn, Lit};
 /// async fn my_handler() -> &'static str {
 ///     "hello world"
 /// }
-/// # fn can_compile_as_axum_handler() {
-/// #     use axum::{Router, routing::get};
-/// #     Router::<()>::new().route("/", get(my_handler));
-/// # }
-/// # fn can_compile_as_aide_operation_handler() {
-/// #     use aide::axum::{ApiRouter, routing::get};
-/// #     ApiRouter::<()>::new().api_route("/", get(my_handler));
-/// # }
 /// ```
 ///
-/// The external dependencies `axum` and `aide` need to be available. Tested
-/// with versions: `axum@0.7.4`, `aide@0.13.2`.
+/// Handlers can be generic, like over their state. The generated code can't
+/// name their generics though, so a return type that depends on them isn't
+/// used to infer the schemas of documented responses.
+///
+/// # Impl Blocks, Traits, and Modules
+///
+/// Handlers that are grouped as associated functions of an impl block can be
+/// documented together by putting the attribute on the impl block. Every
+/// function with a doc comment is documented with the arguments of the
+/// attribute, unless it has an attribute of its own. Methods that take `self`
+/// aren't handlers, so they are left alone. The generated structs can't be in
+/// the impl block, so an associated function can't have the attribute without
+/// it being on the impl block too.
+///
+/// The handlers can use `Self` in their signatures, like for taking the type
+/// as their state.
+///
+/// ```
+/// # use aidecomment::aidecomment;
+/// use aide::axum::{ApiRouter, routing::get};
+/// use axum::extract::State;
+///
+/// #[derive(Clone)]
+/// struct App {
+///     name: String,
+/// }
+///
+/// #[aidecomment]
+/// impl App {
+///     /// Gets the name of the app
+///     async fn name(State(app): State<Self>) -> String {
+///         app.name
+///     }
+///
+///     /// Gets the name of the app as plain text
+///     #[aidecomment(inherit = Self::name)]
+///     async fn name_text(State(app): State<Self>) -> String {
+///         app.name
+///     }
+///
+///     fn router() -> ApiRouter<Self> {
+///         ApiRouter::new()
+///             .api_route("/name", get(Self::name))
+///             .api_route("/name.txt", get(Self::name_text))
+///     }
+/// }
+/// ```
+///
+/// Handlers behind a trait are documented by putting the attribute on both the
+/// trait and its implementations, since their signatures have to match. The
+/// doc comments of the trait document every implementation, and an
+/// implementation with a doc comment of its own replaces the summary and
+/// description with it. The generated structs are named like
+/// `{Trait}_{function}_AideComment` and are next to the trait.
+///
+/// The arguments of the trait are documented like the ones of a function,
+/// and the implementations can document theirs as well, although the
+/// parameters are described by the trait.
+///
+/// On an inline module, every `async fn` with a doc comment is documented.
+/// Functions that have an attribute of their own use it instead.
+///
+/// ```
+/// # use aidecomment::aidecomment;
+/// #[aidecomment(tag = "orders")]
+/// mod orders {
+///     /// Lists orders
+///     pub async fn list() {}
+///
+///     /// Cancels an order
+///     #[aidecomment(tag = "admin")]
+///     pub async fn cancel() {}
+///
+///     // not a handler, so it is left alone
+///     async fn load() {}
+/// }
+/// ```
+///
+/// The doc comment of the module describes the first tag of its handlers,
+/// with an `AideCommentTag` struct in the module like the ones declared by
+/// [`aidecomment_tag!`].
+///
+/// Handlers that are generated by `macro_rules!` can be documented the same
+/// way, with their doc comments and arguments given as fragments.
+///
+/// Doc attributes built with `concat!`, `stringify!`, `env!`, and
+/// `include_str!` are evaluated too, so documentation can be kept in a file.
+/// Paths given to `include_str!` are relative to the source file.
+///
+/// The `file` argument reads the documentation from a markdown file instead,
+/// relative to the source file.
+///
+/// The placeholders `{crate_name}`, `{crate_version}`, and `{env:NAME}` are
+/// replaced when the crate is compiled, with its name and version and the
+/// environment variable `NAME`, and the crate is rebuilt when the variable
+/// changes. Other braces, like the ones of path parameters, are left alone,
+/// and so are code blocks. Doc comments that
+/// already had text like `{crate_version}` outside of code blocks get it
+/// replaced as well, which can be kept as it is by doubling the braces, like
+/// `{{crate_version}}`.
+///
+/// Paragraphs that many handlers share, like notes about authentication, can
+/// be written once as snippets. A `{{include:name}}` directive in the doc
+/// comment is replaced with the file `snippets/name.md` of the crate, which
+/// can include other snippets in turn. The `AIDECOMMENT_SNIPPETS`
+/// environment variable puts the directory somewhere else, relative to the
+/// crate. Constants can be shared with the `use_const` argument instead.
+///
+/// A snippet that doesn't exist, or that ends up including itself, is an
+/// error.
+///
+/// ```compile_fail
+/// # use aidecomment::aidecomment;
+/// /// Deletes a user
+/// ///
+/// /// {{include:missing}}
+/// #[aidecomment]
+/// async fn delete_user() {}
+/// ```
+///
+/// ```compile_fail
+/// # use aidecomment::aidecomment;
+/// /// Deletes a user
+/// ///
+/// /// {{include:cycle}}
+/// #[aidecomment]
+/// async fn delete_user() {}
+/// ```
+///
+/// # Languages
+///
+/// A doc comment can be written in several languages, where a heading like
+/// `# [en]` or `# [de]` starts the text of each. The one documented is chosen
+/// by the `AIDECOMMENT_LANGUAGE` environment variable, and is the first one if
+/// the variable isn't set or the doc comment doesn't have it. Anything before
+/// the first of these headings is shared by every language. If the
+/// `AIDECOMMENT_X_DESCRIPTIONS` environment variable is set, the text of the
+/// other languages is kept in the `x-descriptions` extension, by language.
+///
+/// # Badges
+///
+/// A `[BETA]` or `[EXPERIMENTAL]` marker at the start of the summary is taken
+/// off of it and added to the `x-badges` extension instead, which renderers
+/// like Redoc show next to the operation.
+///
+/// # Sections
+///
+/// Top-level headings in the doc comment that are recognized are taken out of
+/// the description and applied to the relevant part of the operation instead.
+/// Anything in them that isn't understood, as well as any other headings, are
+/// left in the description.
+///
+/// aide infers the responses of the return type of a handler after the
+/// extractors are documented, and reports a conflict for the ones that are
+/// already there, so what the doc comment says about those responses is left
+/// out. A handler with `transform_only` that is routed with [`documented!`],
+/// or a route of [`aidecomment_routes!`], is documented after the responses
+/// are inferred instead, so they keep their content and have the descriptions,
+/// headers, and examples of the doc comment added. The examples below that
+/// use `transform_only` are routed that way.
+///
+/// ## Errors
+///
+/// List items in an `# Errors` section that start with a status code (`404`),
+/// a range (`4XX`), or `default` become the descriptions of those responses.
+///
+/// ```
+/// # use aidecomment::aidecomment;
+/// /// Gets a user
+/// ///
+/// /// # Errors
+/// ///
+/// /// - 404: user not found
+/// /// - 5XX: the database is unavailable
+/// #[aidecomment]
+/// async fn get_user() -> &'static str {
+///     "hello world"
+/// }
+/// ```
+///
+/// ## Responses
+///
+/// A `# Responses` section works the same way as `# Errors` and is meant for
+/// the non-error responses.
+///
+/// ## Returns
+///
+/// The contents of a `# Returns` section become the description of the
+/// successful response, which is the first `2XX` response or `200` if there
+/// isn't one.
+///
+/// Routed with `get` and the like, the successful response is only described
+/// when aide doesn't infer it from the return type.
+///
+/// ## Response Headers
+///
+/// List items in a `# Response Headers` section are headers that are set on
+/// the successful response, or on the response of a sub-heading like `## 201`
+/// that they are under.
+///
+/// ## Request Body
+///
+/// The contents of a `# Request Body` section become the description of the
+/// request body.
+///
+/// A `# Body` section works the same way. Code blocks in it that are tagged
+/// `json`, or another format that examples can be given in, become examples
+/// of the request body.
+///
+/// ## Upload
+///
+/// List items like `- name (type, required): description` in an `# Upload`
+/// section document the fields of a `multipart/form-data` request body, like
+/// the one from axum's `Multipart`. They become the properties of the body's
+/// schema, where the type is one of `string`, `integer`, `number`, `boolean`,
+/// or `binary` for files, and is `string` when it isn't given.
+///
+/// ## Examples
+///
+/// Code blocks tagged `json` in an `# Examples` section become the example of
+/// the request body if there is one, or of the successful response otherwise.
+/// If the line before a code block mentions a "request" or "response", it is
+/// attached to that instead, and a line like `Request:` is removed along with
+/// the code block.
+///
+/// ```
+/// # use aidecomment::aidecomment;
+/// # use axum::Json;
+/// /// Creates a user
+/// ///
+/// /// # Examples
+/// ///
+/// /// Request:
+/// ///
+/// /// ```json
+/// /// "alice"
+/// /// ```
+/// ///
+/// /// Response:
+/// ///
+/// /// ```json
+/// /// { "id": 1, "name": "alice" }
+/// /// ```
+/// #[aidecomment(transform_only)]
+/// async fn create_user(Json(name): Json<String>) -> Json<String> {
+///     Json(name)
+/// }
+/// ```
+///
+/// Code blocks tagged `xml`, `yaml`, `html`, `csv`, or `text` are attached the
+/// same way under their respective media type, with the example as a string.
+///
+/// Code blocks under a sub-heading become named entries in the `examples`
+/// instead, with the sub-heading being taken into account like the line
+/// before.
+///
+/// A sub-heading or line before a code block that starts with a status, like
+/// `### 404` or `404 Not Found:`, attaches it to the response with that status
+/// instead. Any text after the status in a sub-heading names the example.
+///
+/// Code blocks in a language for calling the API, like `curl`, `sh`,
+/// `python`, or `js`, are added to the Redoc `x-codeSamples` extension
+/// instead, labeled by their sub-heading if they have one.
+///
+/// ## Parameters
+///
+/// List items in a `# Parameters` (or `# Arguments`) section of the form
+/// ``- `name`: description`` become the descriptions of the parameters with
+/// that name that were added by the handler's extractors.
+///
+/// ```
+/// # use aidecomment::aidecomment;
+/// # use axum::extract::{Path, Query};
+/// # #[derive(serde::Deserialize, schemars::JsonSchema)]
+/// # struct UserPath { id: u64 }
+/// # #[derive(serde::Deserialize, schemars::JsonSchema)]
+/// # struct Fields { fields: String }
+/// /// Gets a user
+/// ///
+/// /// # Parameters
+/// ///
+/// /// - `id`: the user identifier
+/// /// - `fields`: which fields to include
+/// #[aidecomment]
+/// async fn get_user(Path(path): Path<UserPath>, Query(query): Query<Fields>) -> String {
+///     format!("{} {}", path.id, query.fields)
+/// }
+/// ```
+///
+/// A description that lists the allowed values in backticks after "one of"
+/// also sets them as the `enum` of the parameter's schema.
+///
+/// Constraints in parentheses, like `(min 1, max 100)` or
+/// ``(pattern `^[a-z]+$`)``, are set on the parameter's schema as well.
+///
+/// An `(e.g. 42)` in a description is taken out and becomes the example of
+/// the parameter.
+///
+/// ## Query
+///
+/// List items in a `# Query` section work the same way as `# Parameters` but
+/// only match query parameters, like the ones from a `Query<T>` extractor.
+///
+/// ## Path
+///
+/// List items in a `# Path` section work the same way as `# Query` but only
+/// match path parameters, like the ones from a `Path<T>` extractor.
+///
+/// The parameter of a single-value extractor can be documented on the argument
+/// instead, with `#[aidecomment(doc = "...")]`. It is named after the binding,
+/// unless `name = "..."` names it otherwise, and a `Path` parameter is added
+/// since aide doesn't add one for a single value.
+///
+/// ## Headers
+///
+/// List items in a `# Headers` section work the same way as `# Parameters`
+/// but are for header parameters, which are added if there isn't one already.
+/// A description ending in `required` or `optional` sets whether the header is
+/// required.
+///
+/// ## Cookies
+///
+/// List items in a `# Cookies` section work the same way as `# Headers` but
+/// are for cookie parameters.
+///
+/// ## Protocol
+///
+/// A `# Protocol: websocket` or `# Protocol: sse` heading marks an endpoint
+/// that doesn't respond like a normal REST route. The protocol is added after
+/// the summary and as an `x-protocol` extension, and the description explains
+/// how the connection is upgraded or streamed. WebSocket endpoints also get a
+/// `101` response unless one is documented already.
+///
+/// ## Limits
+///
+/// A list item like ``- request body: 10 MB`` in a `# Limits` section sets the
+/// `x-max-request-size` extension in bytes, and adds the limit to the
+/// description of the request body. Sizes in `KB`, `MB`, and `GB` are in
+/// powers of 1000, and `KiB`, `MiB`, and `GiB` in powers of 1024.
+///
+/// ## Compliance
+///
+/// A `- classification: confidential` list item in a `# Compliance` or
+/// `# Audit` section sets the `x-data-classification` extension, and a
+/// `- pii: email, name` item lists the kinds of personal data the operation
+/// handles in the `x-pii` extension, which is empty for `- pii: none`.
+///
+/// ## SLA
+///
+/// List items like `p99 < 200ms` and `availability: 99.9%` in a `# SLA`
+/// section are added to an `x-sla` extension, with the latency targets in
+/// milliseconds under `latencyMs` and the availability as a percentage.
+///
+/// ## CORS
+///
+/// List items for `origins`, `methods`, `headers`, `credentials`, and
+/// `max age` in a `# CORS` section are added to an `x-cors` extension, as
+/// `allowedOrigins`, `allowedMethods`, `allowedHeaders`, `allowCredentials`,
+/// and `maxAge`. Anything else stays in the description.
+///
+/// ## Caching
+///
+/// List items for caching headers like `Cache-Control` and `ETag` in a
+/// `# Caching` section are headers of the successful response, where a value
+/// like `public, max-age=60` is also the example of `Cache-Control`. The
+/// `x-cacheable` extension is set to `false` if the section mentions
+/// `no-store` or that the response is "not cacheable", and `true` otherwise.
+///
+/// ## Idempotency
+///
+/// An `# Idempotency` section sets the `x-idempotent` extension. It can start
+/// with "yes" or "no", or otherwise says that the operation is "not
+/// idempotent" if it isn't. The section stays in the description, starting
+/// with a sentence that says whether the operation is idempotent.
+///
+/// ## Pagination
+///
+/// A `# Pagination` section adds the standard query parameters for paginating
+/// the results, if the handler doesn't already have them, and describes how
+/// the results are paginated in the successful response along with the
+/// contents of the section. The parameters are `page` and `per_page` by
+/// default, and the `AIDECOMMENT_PAGINATION` environment variable can choose
+/// `offset` for `offset` and `limit`, or `cursor` for `cursor` and `limit`.
+///
+/// ## Security
+///
+/// List items in a `# Security` section name the security schemes that can be
+/// used for the operation, each being an alternative. Schemes that are
+/// required together are joined with `+`.
+///
+/// ## Scopes
+///
+/// List items in a `# Scopes` section are added as the scopes needed for each
+/// scheme of the operation's security requirements.
+///
+/// The requirements can also come from an extractor, since the scopes are
+/// added after the extractors are documented. Scopes are only ever added to
+/// requirements, so without a `# Security` section, a `security` argument,
+/// or an extractor that requires a scheme, they aren't documented at all.
+///
+/// ## Servers
+///
+/// List items in a `# Servers` section that start with a URL are the base
+/// URLs the operation is served from, with anything after the URL describing
+/// it.
+///
+/// ## Callbacks
+///
+/// List items in a `# Callbacks` or `# Webhooks` section are requests the API
+/// makes in response to the operation. They are given as the name of the
+/// callback followed by the method, the URL expression, and a description.
+///
+/// ## Deprecated
+///
+/// An operation is marked as deprecated if the handler has a `#[deprecated]`
+/// attribute or a `# Deprecated` section. The note of the attribute, or the
+/// contents of the section, are put at the start of the description.
+///
+/// ## Since
+///
+/// A `# Since 1.4` heading, or a `# Since` section containing just the version,
+/// is added as an `x-since` extension of the operation. If the
+/// `AIDECOMMENT_TARGET_VERSION` environment variable is set when compiling,
+/// operations that are newer than it are left out of the document when they
+/// are routed with [`documented!`] or [`aidecomment_routes!`]. aide can't leave
+/// out the other ones, so they are marked with `x-internal` instead, which
+/// documentation tools leave out.
+///
+/// ## See Also
+///
+/// The first link in a `# See Also` section becomes the external documentation
+/// of the operation, which is described by the rest of the line. The link can
+/// be a markdown link, a URL in `<>` brackets, or a plain URL.
+///
+/// ## Changelog
+///
+/// List items in a `# Changelog` section are added to an `x-changelog`
+/// extension instead of the description, with the part before a `:` taken as
+/// the version.
+///
+/// ## Rate Limits
+///
+/// List items like `100 requests/minute per token` in a `# Rate Limits`
+/// section are added to an `x-rate-limit` extension, and are summarized in a
+/// paragraph at the end of the description.
+///
+/// ## Permissions
+///
+/// List items in a `# Permissions` section are added to an `x-permissions`
+/// extension as the roles that are needed to call the operation. Anything
+/// after the first word describes the role.
+///
+/// ## Panics
+///
+/// If the `AIDECOMMENT_PANICS_AS_500` environment variable is set when
+/// compiling, the contents of a `# Panics` section become the description of
+/// the `500` response. It can be set for a whole project in the `[env]` table
+/// of `.cargo/config.toml`:
+///
+/// ```toml
+/// [env]
+/// AIDECOMMENT_PANICS_AS_500 = "1"
+/// ```
+///
+/// Otherwise the section is left out like the other rustdoc sections, and no
+/// `500` response is documented.
+///
+/// ## Rustdoc Sections
+///
+/// Sections that only make sense to Rust callers are left out of the
+/// description. These are `# Safety` and `# Panics` by default, and can be
+/// changed with a comma-separated `AIDECOMMENT_STRIP_SECTIONS` environment
+/// variable. The contents of recognized sections like `# Examples` are still
+/// used if they are stripped, but anything that isn't understood is left out.
+///
+/// # Directives
+///
+/// A line like `@x-audit-level: high` anywhere in the doc comment sets that
+/// extension of the operation and is removed from the description. The value
+/// is read like in YAML, so it can be a number, a `[list]`, or quoted, and a
+/// directive without a value is `true`.
+///
+/// Directives can also be given in `<!-- aide: ... -->` comments, which
+/// rustdoc doesn't show. These contain `key=value` entries, separated by
+/// spaces, that can set extensions as well as `tag` (or `tags` separated by
+/// commas), `operation_id`, and `deprecated`. Values with spaces can be
+/// quoted.
+///
+/// # Tags
+///
+/// With the `jsdoc` argument, or the `AIDECOMMENT_JSDOC` environment variable
+/// for every handler, JSDoc-style tags can be used instead of sections. A tag
+/// starts a line and continues until a blank line, the next tag, a heading, or
+/// a list item. These are understood:
+///
+/// - `@param`, or `@query`, `@path`, `@header`, and `@cookie` for a specific
+///   location, followed by the name and description of a parameter, where a
+///   `[name]` is optional
+/// - `@returns` with the description of the successful response
+/// - `@throws` or `@response` with a status and its description
+/// - `@deprecated`, `@since`, `@see`, `@tags`, and `@operationId`
+///
+/// A `{Type}` after the tag is ignored, since the types are known from the
+/// handler.
+///
+/// ```
+/// # use aidecomment::aidecomment;
+/// # use axum::{extract::Query, Json};
+/// # #[derive(serde::Deserialize, schemars::JsonSchema)]
+/// # struct Search { name: String }
+/// /// Searches users
+/// ///
+/// /// @param {string} name - part of the name of the user
+/// /// @returns the users that were found
+/// /// @throws 400 the name is empty
+/// /// @tags users
+/// #[aidecomment(transform_only, jsdoc)]
+/// async fn search_users(Query(search): Query<Search>) -> Json<Vec<String>> {
+///     Json(vec![search.name])
+/// }
+/// ```
+///
+/// Otherwise, lines that start with a tag are left in the description.
+///
+/// # Front Matter
+///
+/// A ```` ```yaml ```` code block at the very start of the doc comment sets
+/// fields of the operation directly. These are `tags`, `operationId`,
+/// `summary`, `description`, `deprecated`, `externalDocs`, `servers`,
+/// `security`, and any `x-` extensions, and they take precedence over the rest
+/// of the doc comment.
+///
+/// ```
+/// # use aidecomment::aidecomment;
+/// /// ```yaml
+/// /// operationId: getUser
+/// /// tags: [users]
+/// /// x-audience:
+/// ///   - internal
+/// ///   - partner
+/// /// ```
+/// ///
+/// /// Gets a user
+/// #[aidecomment]
+/// async fn get_user() {}
+/// ```
+///
+/// # Arguments
+///
+/// Arguments of the attribute add to what the doc comment documents, or take
+/// precedence over it.
+///
+/// - `summary = "..."` and `description = "..."` replace the summary and
+///   description from the doc comment, for when it is written for Rust
+///   developers instead of the consumers of the API
+/// - `description_only` doesn't split off the first paragraph as the summary,
+///   keeping all of the comment in the description and leaving the summary
+///   unset
+/// - `summary_only` leaves the description unset, for when the rest of the
+///   comment is only meant for Rust developers
+/// - `summary_mode = "first_sentence"` makes the summary only the first
+///   sentence of the first paragraph, and the rest of it part of the
+///   description. The default is `"first_paragraph"`, and the
+///   `AIDECOMMENT_SUMMARY_MODE` environment variable sets it for every handler.
+/// - `merge = "replace"`, `"append"`, or `"fill"` says whether the summary and
+///   description replace the ones that extractors set, are added to their
+///   end, or are only set if there are none. The default is `"replace"`.
+/// - `summary_prefix = "..."` and `summary_suffix = "..."` are added around
+///   the summary, for marking a group of operations the same way
+/// - `tag = "..."` adds a tag to the operation, and can be repeated
+/// - `operation_id = "..."` sets the operation id, so that it can stay the same
+///   when the handler is renamed
+/// - `struct_name = "..."` names the struct that wraps the last argument,
+///   which is otherwise `{handler}_AideComment`
+/// - `vis = "pub(crate)"` sets the visibility of that struct, which is
+///   otherwise the same as the handler's
+/// - `attach = Type` implements `OperationInput` for an extractor that the
+///   handler already takes instead of wrapping its last argument. That type
+///   can't implement `OperationInput` itself, and the documentation applies
+///   to every handler that takes it.
+/// - `inherit = handler` uses the summary and description of another handler
+///   with this attribute, where the doc comment is added to the description
+///   and the `summary` argument can replace the summary. An associated
+///   function can be named like `Self::handler` or `Type::handler`.
+/// - `use_const = DOCS` adds the text of a `&str` constant, which can be
+///   built with `concat!` or `include_str!`, for documentation that many
+///   handlers share. Its first paragraph is the summary if the handler
+///   doesn't have one, and the rest is added to the end of the description.
+///   The constant isn't known until the handler is compiled, so its text
+///   isn't parsed for sections like the doc comment is.
+/// - `file = "docs/handler.md"` reads a markdown file, relative to the source
+///   file like `include_str!`, as if it were written in the doc comment. Its
+///   first paragraph is the summary unless the handler has a doc comment,
+///   which the file continues, and its sections are recognized as well.
+/// - `aide = path` and `axum = path` say where the crates are when they are
+///   renamed or re-exported, which the `AIDECOMMENT_AIDE_PATH` and
+///   `AIDECOMMENT_AXUM_PATH` environment variables do for every handler
+/// - `auto_operation_id` uses the name of the handler as the operation id if
+///   none is documented, which the `AIDECOMMENT_AUTO_OPERATION_ID`
+///   environment variable does for every handler
+/// - `jsdoc` understands the [JSDoc-style tags](#tags) of the doc comment,
+///   which the `AIDECOMMENT_JSDOC` environment variable does for every
+///   handler
+/// - `module_tag` tags the operation with the name of the module that the
+///   handler is in, and `module_tag(case = "title", prefix = "...")` writes
+///   it in `snake`, `kebab`, `title`, or `pascal` case after a prefix. The
+///   `AIDECOMMENT_MODULE_TAGS`, `AIDECOMMENT_MODULE_TAG_CASE`, and
+///   `AIDECOMMENT_MODULE_TAG_PREFIX` environment variables do this for every
+///   handler. Handlers at the root of the crate aren't tagged.
+/// - `deprecated` or `deprecated = "..."` marks the operation as deprecated
+///   with an optional note, without deprecating the handler in Rust
+/// - `security("scheme", scopes("..."))` adds a security requirement that
+///   replaces the documented ones, where the scopes belong to the scheme
+///   before them. Schemes in the same argument are needed together, and
+///   repeating the argument lists alternatives.
+/// - `auth = "bearer"`, `"api_key"`, `"basic"`, or `"none"` is a shorthand
+///   for requiring the `bearer_auth`, `api_key`, or `basic_auth` security
+///   scheme, or none at all, which also adds a sentence to the description
+/// - `response(status = 404, description = "...")` documents a response,
+///   replacing one with the same status from the doc comment, and can be
+///   repeated. The status can also be a string like `"4XX"` or `"default"`.
+/// - `consumes = "..."` and `produces = "..."` replace the media types that
+///   aide infers for the request body and the successful responses, keeping
+///   their schemas, and can be repeated
+/// - `external_docs(url = "...", description = "...")` links to further
+///   documentation, where the description is optional
+/// - `servers("...", ...)` or `server(url = "...", description = "...")`
+///   replace the documented servers, for operations that are served from
+///   another host, and `server` can be repeated
+/// - `extensions(x_owner = "payments", x_tier = 2)` sets vendor extensions,
+///   where the underscores in the names are dashes, to strings, numbers,
+///   booleans, or arrays of them
+/// - `response_ty = Type` names the type that the handler responds with when
+///   it returns an `impl IntoApiResponse`, so that the responses that aide
+///   infers for it are left for aide to add
+/// - `error = Type` documents the responses of an error enum that derives
+///   [`ErrorResponses`], before the doc comment is applied
+/// - `params = Type` describes the parameters of an extractor type that
+///   derives [`AideCommentParams`] with the doc comments of its fields, before
+///   the doc comment is applied. It can be repeated.
+/// - `transform = ...` calls a function or closure with an aide
+///   `TransformOperation` after the documentation is applied, for anything
+///   else that needs to be customized. Hiding the operation with it has no
+///   effect.
+/// - `transform_only` leaves the arguments of the handler as they are, and
+///   instead generates a `docs` function on its struct for aide's `get_with`
+///   and the like, which [`documented!`] routes it with
+/// - `hidden` marks the operation with the `x-internal` extension, which tools
+///   that generate public documentation leave out
+/// - `method(HEAD, summary = "...")` documents a handler differently for one
+///   of the methods it is routed for, with a `summary`, `description`,
+///   `operation_id`, or `deprecated`. aide doesn't tell which method the
+///   documentation is for, so these are applied by passing the `methods`
+///   function of the generated struct to `api_route_with`.
+/// - `audience = "internal"`, `"public"`, or `"partner"` says who the
+///   operation is meant for with the `x-audience` extension. When the
+///   `AIDECOMMENT_AUDIENCE` environment variable lists the audiences that the
+///   document is generated for, like `public,partner`, operations for other
+///   audiences are marked with `x-internal` as if they were `hidden`.
+/// - `cfg = "docs"` only generates the documentation when the `docs` feature
+///   is enabled, leaving the handler as it is otherwise. Any other predicate
+///   can be given with `cfg(...)`, like `cfg(all(feature = "docs", not(test)))`.
+/// - `debug` shows the summary and description that the comment was parsed
+///   into, along with the signature of the handler and the code that was
+///   generated, in a warning on the handler
+///
+/// ```
+/// # use aidecomment::aidecomment;
+/// /// Gets a user
+/// #[aidecomment(struct_name = "GetUserDocs")]
+/// async fn get_user() {}
+///
+/// /// Gets an order
+/// #[aidecomment(struct_name = "GetOrderDocs", transform_only)]
+/// async fn get_order() {}
+///
+/// use aide::axum::{ApiRouter, routing::{get, get_with}};
+///
+/// let docs: GetUserDocs = GetUserDocs(());
+/// let router = ApiRouter::<()>::new()
+///     .api_route("/user", get(get_user))
+///     .api_route("/order", get_with(get_order, GetOrderDocs::docs));
+/// ```
+///
+/// The name is used as it is, so two handlers can't be given the same one.
+///
+/// ```compile_fail
+/// # use aidecomment::aidecomment;
+/// /// Gets a user
+/// #[aidecomment(struct_name = "Docs")]
+/// async fn get_user() {}
+///
+/// /// Gets an order
+/// #[aidecomment(struct_name = "Docs")]
+/// async fn get_order() {}
+/// ```
+///
+/// The external dependencies `axum` and `aide` need to be available, unless
+/// the `aide` and `axum` arguments say where they are. Tested with versions:
+/// `axum@0.7.4`, `aide@0.13.2`.
 #[proc_macro_attribute]
-pub fn aidecomment(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    let mut fn_def = syn::parse_macro_input!(item as ItemFn);
+pub fn aidecomment(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr = proc_macro2::TokenStream::from(attr);
+    let expanded = match syn::parse_macro_input!(item as Item) {
+        // the struct can't be generated inside of an impl block
+        Item::Fn(fn_def) if is_associated(&fn_def.sig) => Err(syn::Error::new_spanned(
+            &fn_def.sig,
+            "an associated function is documented by putting `#[aidecomment]` on its impl block",
+        )),
+        Item::Fn(mut fn_def) => syn::parse2::<Args>(attr).and_then(|args| {
+            let cfg = args.cfg.clone();
+            let mut original = fn_def.clone();
+            params::argument_docs(&mut original.sig)?;
+            let items = handler(
+                args,
+                &fn_def.attrs,
+                &fn_def.vis,
+                &mut fn_def.sig,
+                Owner::Function(None),
+            )?;
+            let fn_def = with_original(cfg.as_ref(), &fn_def, &original);
+
+            Ok(quote! {
+                #items
+                #fn_def
+            })
+        }),
+        Item::Impl(impl_def) => match impl_def.trait_.clone() {
+            Some((_, path, _)) => trait_impl(attr, impl_def, &path),
+            None => impl_block(attr, impl_def),
+        },
+        Item::Trait(trait_def) => trait_block(attr, trait_def),
+        Item::Mod(module) => module_block(attr, module),
+        item => Err(syn::Error::new_spanned(
+            item,
+            "`aidecomment` can only be used on a function, an impl block, a trait, or a module",
+        )),
+    };
+
+    with_tracked_files(expanded)
+}
+
+/// The generated items, along with the ones that have the crate rebuilt when
+/// the files or environment variables that were read for the documentation
+/// change.
+fn with_tracked_files(expanded: syn::Result<proc_macro2::TokenStream>) -> TokenStream {
+    let expanded = expanded.unwrap_or_else(syn::Error::into_compile_error);
+    let tracked = tracked();
+    quote!(#expanded #tracked).into()
+}
+
+/// Like [`with_tracked_files`], for a macro that expands to an expression.
+fn expression_with_tracked_files(expanded: syn::Result<proc_macro2::TokenStream>) -> TokenStream {
+    let expanded = expanded.unwrap_or_else(syn::Error::into_compile_error);
+    let tracked = tracked();
+    if tracked.is_empty() {
+        return expanded.into();
+    }
+    quote!({ #tracked #expanded }).into()
+}
+
+/// The items that track what was read for the documentation.
+fn tracked() -> proc_macro2::TokenStream {
+    let mut tracked = comment::tracked_files();
+    tracked.extend(config::tracked_variables());
+    tracked
+}
+
+/// Documents every associated function of an impl block that has a doc
+/// comment, as if each of them had the attribute.
+///
+/// A function with its own attribute uses its arguments instead.
+fn impl_block(
+    attr: proc_macro2::TokenStream,
+    mut impl_def: ItemImpl,
+) -> syn::Result<proc_macro2::TokenStream> {
+    // the generated structs are outside of the impl block, where its generics
+    // can't be named
+    if !impl_def.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &impl_def.generics,
+            "`aidecomment` can't be used on an impl block with generics",
+        ));
+    }
+
+    shared_args(&attr, "an impl block")?;
+
+    let mut items = Vec::new();
+    for item in &mut impl_def.items {
+        let ImplItem::Fn(method) = item else {
+            continue;
+        };
 
-    let doc_comments = fn_def
-        .attrs
+        // methods aren't handlers, and neither are undocumented functions
+        let Some(args) = own_args(&attr, &mut method.attrs, &method.sig, true)? else {
+            continue;
+        };
+
+        let cfg = args.cfg.clone();
+        let mut original = method.clone();
+        params::argument_docs(&mut original.sig)?;
+        items.push(handler(
+            args,
+            &method.attrs,
+            &method.vis,
+            &mut method.sig,
+            Owner::Function(Some(&impl_def.self_ty)),
+        )?);
+        *item = ImplItem::Verbatim(with_original(cfg.as_ref(), &*method, &original));
+    }
+
+    Ok(quote! {
+        #(#items)*
+        #impl_def
+    })
+}
+
+/// Documents every `async fn` of a module that has a doc comment, as if each
+/// of them had the attribute.
+///
+/// A function with its own attribute uses its arguments instead.
+fn module_block(
+    attr: proc_macro2::TokenStream,
+    mut module: ItemMod,
+) -> syn::Result<proc_macro2::TokenStream> {
+    shared_args(&attr, "a module")?;
+    let shared = syn::parse2::<Args>(attr.clone())?;
+    let description = tag::description(&module.attrs)?;
+    let Some((_, items)) = &mut module.content else {
+        return Err(syn::Error::new_spanned(
+            &module,
+            "`aidecomment` can only be used on a module whose items are inline",
+        ));
+    };
+
+    // the doc comment of the module describes the tag of its handlers
+    if let (Some(name), false) = (shared.tags.first(), description.is_empty()) {
+        let tag = tag::tag_struct(
+            &[parse_quote!(#[doc = "The tag of the handlers of this module."])],
+            &parse_quote!(pub),
+            &Ident::new("AideCommentTag", Span::call_site()),
+            name,
+            &description,
+            None,
+        )?;
+        items.push(Item::Verbatim(tag));
+    }
+
+    for item in items {
+        let Item::Fn(fn_def) = item else {
+            continue;
+        };
+        let has_own = fn_def
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("aidecomment"));
+        if fn_def.sig.asyncness.is_none() && !has_own {
+            continue;
+        }
+        let Some(args) = own_args(&attr, &mut fn_def.attrs, &fn_def.sig, true)? else {
+            continue;
+        };
+
+        let cfg = args.cfg.clone();
+        let mut original = fn_def.clone();
+        params::argument_docs(&mut original.sig)?;
+        let generated = handler(
+            args,
+            &fn_def.attrs,
+            &fn_def.vis,
+            &mut fn_def.sig,
+            Owner::Function(None),
+        )?;
+        let fn_def = with_original(cfg.as_ref(), &*fn_def, &original);
+        *item = Item::Verbatim(quote! {
+            #generated
+            #fn_def
+        });
+    }
+
+    Ok(module.into_token_stream())
+}
+
+/// Documents every associated function of a trait, whether or not it has a
+/// doc comment, since its implementations can document it as well.
+///
+/// Each function gets a constant with the summary and description that its
+/// implementations document, which they replace the ones of the trait with.
+fn trait_block(
+    attr: proc_macro2::TokenStream,
+    mut trait_def: ItemTrait,
+) -> syn::Result<proc_macro2::TokenStream> {
+    // the generated structs are outside of the trait, where its generics
+    // can't be named
+    if !trait_def.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &trait_def.generics,
+            "`aidecomment` can't be used on a trait with generics",
+        ));
+    }
+    shared_args(&attr, "a trait")?;
+
+    let mut items = Vec::new();
+    let mut docs = Vec::new();
+    for item in &mut trait_def.items {
+        let TraitItem::Fn(method) = item else {
+            continue;
+        };
+        let Some(args) = own_args(&attr, &mut method.attrs, &method.sig, false)? else {
+            continue;
+        };
+
+        let cfg = args.cfg.clone();
+        let mut original = method.clone();
+        params::argument_docs(&mut original.sig)?;
+        items.push(handler(
+            args,
+            &method.attrs,
+            &trait_def.vis,
+            &mut method.sig,
+            Owner::Trait {
+                name: &trait_def.ident,
+                has_body: method.default.is_some(),
+            },
+        )?);
+        let doc = trait_doc(&method.sig.ident);
+        *item = TraitItem::Verbatim(with_original(cfg.as_ref(), &*method, &original));
+
+        docs.push(TraitItem::Verbatim(quote! {
+            #[doc(hidden)]
+            const #doc: ::core::option::Option<(&'static str, &'static str)> = ::core::option::Option::None;
+        }));
+    }
+    trait_def.items.extend(docs);
+
+    Ok(quote! {
+        #(#items)*
+        #trait_def
+    })
+}
+
+/// Wraps the arguments of the associated functions of an implementation the
+/// same way as the trait, and replaces the summary and description of the
+/// ones that have a doc comment.
+fn trait_impl(
+    attr: proc_macro2::TokenStream,
+    mut impl_def: ItemImpl,
+    trait_path: &syn::Path,
+) -> syn::Result<proc_macro2::TokenStream> {
+    shared_args(&attr, "an impl block")?;
+    let Some(trait_name) = trait_path
+        .segments
+        .last()
+        .map(|segment| segment.ident.clone())
+    else {
+        return Err(syn::Error::new_spanned(trait_path, "expected a trait"));
+    };
+
+    let mut docs = Vec::new();
+    for item in &mut impl_def.items {
+        let ImplItem::Fn(method) = item else {
+            continue;
+        };
+        let Some(args) = own_args(&attr, &mut method.attrs, &method.sig, false)? else {
+            continue;
+        };
+
+        // the structs are next to the trait
+        let mut struct_name = trait_path.clone();
+        if let Some(segment) = struct_name.segments.last_mut() {
+            segment.ident = trait_struct_name(&trait_name, &method.sig.ident);
+            segment.arguments = syn::PathArguments::None;
+        }
+
+        let cfg = args.cfg.clone();
+        // the parameters are documented by the trait, so the documentation of
+        // the arguments is only taken off of them
+        params::argument_docs(&mut method.sig)?;
+        let original = method.clone();
+        match method.sig.inputs.last_mut() {
+            Some(FnArg::Typed(arg)) => {
+                let pat = &arg.pat;
+                let ty = &arg.ty;
+                *arg.pat = parse_quote!(#struct_name(#pat, _));
+                *arg.ty = parse_quote!(#struct_name<#ty, Self>);
+            }
+            _ => method
+                .sig
+                .inputs
+                .push(parse_quote!(_: #struct_name<(), Self>)),
+        }
+        if !comment::doc_lines(&method.attrs)?.is_empty() {
+            let doc_name = trait_doc(&method.sig.ident);
+            let doc = operation_doc(args, &method.attrs, &method.sig.ident)?;
+            let summary = &doc.summary;
+            let description = &doc.description;
+            docs.push(ImplItem::Verbatim(quote! {
+                const #doc_name: ::core::option::Option<(&'static str, &'static str)> = ::core::option::Option::Some((#summary, #description));
+            }));
+        }
+        *item = ImplItem::Verbatim(with_original(cfg.as_ref(), &*method, &original));
+    }
+    impl_def.items.extend(docs);
+
+    Ok(impl_def.into_token_stream())
+}
+
+/// Checks the arguments of an attribute on a group of handlers, which can't
+/// be the ones that are only for a single handler.
+fn shared_args(attr: &proc_macro2::TokenStream, target: &str) -> syn::Result<()> {
+    let shared = syn::parse2::<Args>(attr.clone())?;
+    let single = [
+        ("struct_name", shared.struct_name.is_some()),
+        ("operation_id", shared.operation_id.is_some()),
+        ("attach", shared.attach.is_some()),
+        ("method", !shared.methods.is_empty()),
+    ];
+    match single.iter().find(|(_, is_set)| *is_set) {
+        Some((name, _)) => Err(syn::Error::new(
+            Span::call_site(),
+            format!("`{name}` can't be used on {target}, since it is for a single handler"),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// The arguments of a function in a group of handlers, which are its own if
+/// it has an attribute, or the ones of the group otherwise.
+///
+/// This is `None` for functions that aren't handlers, which are methods and,
+/// if `documented` is set, functions without a doc comment.
+fn own_args(
+    attr: &proc_macro2::TokenStream,
+    attrs: &mut Vec<Attribute>,
+    sig: &Signature,
+    documented: bool,
+) -> syn::Result<Option<Args>> {
+    let own = attrs
         .iter()
-        .filter_map(|attr| match &attr.meta {
-            syn::Meta::NameValue(nvmeta) => Some(nvmeta),
-            _ => None,
-        })
-        .filter(|nvmeta| nvmeta.path.get_ident().map(|i| i.to_string()) == Some("doc".to_owned()))
-        .filter_map(|nvmeta| match &nvmeta.value {
-            Expr::Lit(literal) => Some(literal),
-            _ => None,
-        })
-        .filter_map(|literal| match &literal.lit {
-            Lit::Str(string) => Some(string.value()),
-            _ => None,
+        .position(|attr| attr.path().is_ident("aidecomment"));
+    match own {
+        Some(index) => match &attrs.remove(index).meta {
+            Meta::Path(_) => Ok(Some(Args::default())),
+            meta => syn::parse2(meta.require_list()?.tokens.clone()).map(Some),
+        },
+        None if sig.receiver().is_some() => Ok(None),
+        None if documented && comment::doc_lines(attrs)?.is_empty() => Ok(None),
+        None => syn::parse2(attr.clone()).map(Some),
+    }
+}
+
+/// The name of the struct generated for an associated function of a trait.
+fn trait_struct_name(trait_name: &Ident, method: &Ident) -> Ident {
+    generated_ident(
+        &format!("{}_{}_AideComment", trait_name.unraw(), method.unraw()),
+        method,
+    )
+}
+
+/// An identifier for generated code, which is located at the one it is named
+/// after so that errors point to it, even when the handler is generated by
+/// `macro_rules!`.
+fn generated_ident(name: &str, near: &Ident) -> Ident {
+    Ident::new(name, Span::mixed_site().located_at(near.span()))
+}
+
+/// The name of the constant that an implementation of a trait documents an
+/// associated function with.
+fn trait_doc(method: &Ident) -> Ident {
+    let name = format!("AIDECOMMENT_{}", method.unraw().to_string().to_uppercase());
+    generated_ident(&name, method)
+}
+
+/// Where a handler is defined.
+#[derive(Clone, Copy)]
+enum Owner<'a> {
+    /// A function, or an associated function of the type of an impl block.
+    Function(Option<&'a Type>),
+    /// An associated function of a trait, which its implementations can
+    /// document differently.
+    Trait { name: &'a Ident, has_body: bool },
+}
+
+/// Parses the doc comment of a handler, along with the arguments that add to
+/// it.
+fn operation_doc(args: Args, attrs: &[Attribute], handler: &Ident) -> syn::Result<OperationDoc> {
+    let mut lines = comment::doc_lines(attrs)?;
+    if let Some(file) = &args.file {
+        let text = comment::read_relative(&file.value(), file.span(), file)?;
+        // the file continues the doc comment as if it were another paragraph
+        if !lines.is_empty() {
+            lines.push(comment::Line {
+                text: String::new(),
+                span: file.span(),
+            });
+        }
+        lines.extend(comment::expand(comment::text_lines(&text, file.span()))?);
+    }
+    let (lines, translations) = comment::select_language(lines, config::language().as_deref());
+    let mut doc = DocComment::parse(lines, args.jsdoc || config::jsdoc());
+    match args.summary_mode()? {
+        SummaryMode::FirstSentence => doc.split_first_sentence(),
+        SummaryMode::FirstParagraph => {}
+    }
+    let mut doc = OperationDoc::from_comment(doc)?;
+    doc.module_tag = args.module_tag()?;
+    if config::x_descriptions() && !translations.is_empty() {
+        let descriptions = translations
+            .into_iter()
+            .map(|(language, text)| (language, text.into()))
+            .collect::<serde_json::Map<_, _>>();
+        doc.extensions
+            .push(("x-descriptions".to_owned(), descriptions.into()));
+    }
+
+    // a `# Deprecated` section explains it better than the attribute
+    if let Some(note) = comment::deprecation(attrs)? {
+        doc.deprecated.get_or_insert(note);
+    }
+
+    args.apply(&mut doc, &handler.unraw().to_string());
+    Ok(doc)
+}
+
+/// The handler, along with the original one for when the documentation isn't
+/// generated because of the `cfg` argument.
+fn with_original(
+    cfg: Option<&proc_macro2::TokenStream>,
+    handler: &impl ToTokens,
+    original: &impl ToTokens,
+) -> proc_macro2::TokenStream {
+    match cfg {
+        Some(cfg) => quote! {
+            #[cfg(#cfg)]
+            #handler
+            #[cfg(not(#cfg))]
+            #original
+        },
+        None => handler.to_token_stream(),
+    }
+}
+
+/// Generates the documentation of a handler, wrapping its last argument if it
+/// isn't attached to an extractor.
+///
+/// The struct of an associated function of a trait has another parameter for
+/// the implementation, whose documentation replaces the summary and
+/// description of the trait.
+fn handler(
+    args: Args,
+    attrs: &[Attribute],
+    fn_vis: &Visibility,
+    sig: &mut Signature,
+    owner: Owner,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let aide = args.crate_path("aide")?;
+    let axum = args.crate_path("axum")?;
+    let transform = args.transform.clone().map(|transform| {
+        quote! {
+            let _ = (#transform)(__aide::transform::TransformOperation::new(operation));
+        }
+    });
+    let struct_name = args.struct_name.clone().unwrap_or_else(|| match owner {
+        Owner::Trait { name, .. } => trait_struct_name(name, &sig.ident),
+        Owner::Function(_) => {
+            generated_ident(&format!("{}_AideComment", sig.ident.unraw()), &sig.ident)
+        }
+    });
+    let vis = args.vis.clone().unwrap_or_else(|| fn_vis.clone());
+    let attach = args.attach.clone();
+    // the signature is rewritten below, so the one that was written is kept
+    let debug = args.debug.then(|| sig.to_token_stream().to_string());
+    let cfg = args.cfg.as_ref().map(|cfg| quote!(#[cfg(#cfg)]));
+    let response_ty = args.response_ty.clone();
+    // these come first so that the doc comment can describe them differently
+    let params = &args.params;
+    let parameters = quote! {
+        #(
+            for (name, description) in <#params>::AIDECOMMENT_PARAMETERS {
+                let parameter = operation.parameters.iter_mut().find_map(|parameter| match parameter {
+                    __aide::openapi::ReferenceOr::Item(parameter) if parameter.parameter_data_ref().name == *name => Some(parameter),
+                    _ => None,
+                });
+                if let Some(parameter) = parameter {
+                    parameter.parameter_data_mut().description = Some((*description).to_owned());
+                }
+            }
+        )*
+    };
+    let error_responses = args.error.clone().map(|error| {
+        quote! {
+            let responses = operation.responses.get_or_insert_with(::core::default::Default::default);
+            for (code, description) in <#error>::AIDECOMMENT_RESPONSES {
+                if pending_responses.contains(&Some(*code)) {
+                    continue;
+                }
+                let response = responses
+                    .responses
+                    .entry(__aide::openapi::StatusCode::Code(*code))
+                    .or_insert_with(|| __aide::openapi::ReferenceOr::Item(::core::default::Default::default()));
+                if let __aide::openapi::ReferenceOr::Item(response) = response {
+                    response.description = (*description).to_owned();
+                }
+            }
+        }
+    });
+    // the summary may come from another handler, so this is done last
+    let summary_affixes = (args.summary_prefix.is_some() || args.summary_suffix.is_some()).then(|| {
+        let prefix = args.summary_prefix.as_deref().unwrap_or_default();
+        let suffix = args.summary_suffix.as_deref().unwrap_or_default();
+
+        quote! {
+            if let Some(summary) = operation.summary.as_mut().filter(|summary| !summary.is_empty()) {
+                *summary = format!("{}{}{}", #prefix, summary, #suffix);
+            }
+        }
+    });
+    let inherit = args.inherit.clone().map(|handler| {
+        // the documentation is kept on the struct generated for the handler
+        let handler = struct_path(handler);
+
+        quote! {
+            if operation.summary.as_deref().map_or(true, str::is_empty) {
+                operation.summary = Some(#handler::<()>::SUMMARY.to_owned());
+            }
+            let description = operation.description.take().unwrap_or_default();
+            operation.description = Some(format!("{}\n\n{}", #handler::<()>::DESCRIPTION, description).trim().to_owned());
+        }
+    });
+    // the value of the constant is only known where the code is generated,
+    // so it is split into the summary and description there
+    let use_const = args.use_const.clone().map(|docs| {
+        quote! {
+            let docs: &str = #docs;
+            let docs = docs.trim();
+            let description = if operation.summary.as_deref().map_or(true, str::is_empty) {
+                let (summary, description) = docs.split_once("\n\n").unwrap_or((docs, ""));
+                operation.summary = Some(summary.split_whitespace().collect::<Vec<_>>().join(" "));
+                description
+            } else {
+                docs
+            };
+            let own = operation.description.take().unwrap_or_default();
+            operation.description = Some(format!("{}\n\n{}", own, description.trim()).trim().to_owned());
+        }
+    });
+
+    if args.transform_only {
+        let conflict = match owner {
+            Owner::Trait { .. } => Some("on a trait"),
+            Owner::Function(_) if attach.is_some() => Some("with `attach`"),
+            Owner::Function(_) if cfg.is_some() => Some("with `cfg`"),
+            Owner::Function(_) => None,
+        };
+        if let Some(conflict) = conflict {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                format!("`transform_only` can't be used {conflict}"),
+            ));
+        }
+    }
+    let transform_only = args.transform_only;
+
+    if let (Some(method), Some(_)) = (args.methods.first(), &attach) {
+        return Err(syn::Error::new_spanned(
+            &method.method,
+            "`method` can't be used with `attach`",
+        ));
+    }
+    // aide doesn't say which method an operation is for, so these are applied
+    // to the path item after it is routed
+    let methods = (!args.methods.is_empty()).then(|| {
+        let overrides = args.methods.iter().map(|doc| {
+            let method = &doc.method;
+            let summary = doc.summary.iter();
+            let description = doc.description.iter();
+            let operation_id = doc.operation_id.iter();
+            let deprecated = doc.deprecated.then_some(true).into_iter();
+
+            quote! {
+                if let Some(operation) = item.#method.as_mut() {
+                    #(operation.summary = Some(#summary.to_owned());)*
+                    #(operation.description = Some(#description.to_owned());)*
+                    #(operation.operation_id = Some(#operation_id.to_owned());)*
+                    #(operation.deprecated = #deprecated;)*
+                }
+            }
+        });
+
+        quote! {
+            impl #struct_name {
+                /// Documents the methods that are documented differently, for
+                /// `api_route_with`.
+                pub fn methods(mut path_item: __aide::transform::TransformPathItem<'_>) -> __aide::transform::TransformPathItem<'_> {
+                    let item = path_item.inner_mut();
+                    #(#overrides)*
+                    path_item
+                }
+            }
+        }
+    });
+
+    if let (Owner::Trait { .. }, Some(_)) = (owner, &attach) {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`attach` can't be used on a trait, since its implementations need the same signature",
+        ));
+    }
+    // `H` is the implementation of the trait
+    let (params, bound, generics, wrap) = match owner {
+        Owner::Trait { name, .. } => (
+            quote!(<T = (), H: ?Sized = ()>(T, ::core::marker::PhantomData<fn() -> *const H>)),
+            quote!(, H: ?Sized + #name),
+            quote!(<T, H>),
+            quote!(|inner| #struct_name(inner, ::core::marker::PhantomData)),
+        ),
+        Owner::Function(_) => (
+            quote!(<T = ()>(T)),
+            quote!(),
+            quote!(<T>),
+            quote!(#struct_name),
+        ),
+    };
+    let implementation_doc = match owner {
+        Owner::Trait { name, .. } => {
+            let doc = trait_doc(&sig.ident);
+            Some(quote! {
+                if let Some((summary, description)) = <H as #name>::#doc {
+                    operation.summary = Some(summary.to_owned());
+                    operation.description = Some(description.to_owned());
+                }
+            })
+        }
+        Owner::Function(_) => None,
+    };
+
+    // the arguments can document the parameters that they extract
+    let arguments = params::argument_docs(sig)?;
+    let mut doc = operation_doc(args, attrs, &sig.ident)?;
+    doc.parameters.extend(arguments);
+
+    let output = match &sig.output {
+        _ if response_ty.is_some() => response_ty.clone(),
+        ReturnType::Type(_, ty) if !matches!(**ty, Type::ImplTrait(_)) => Some((**ty).clone()),
+        _ => None,
+    };
+    // the documentation is generated outside of the impl block or trait
+    let self_ty = match owner {
+        Owner::Trait { .. } => Some(parse_quote!(H)),
+        Owner::Function(self_ty) => self_ty.cloned(),
+    };
+    let output = match (output, self_ty) {
+        (Some(output), Some(self_ty)) => Some(syn::parse2(replace_self(
+            output.into_token_stream(),
+            &self_ty,
+        ))?),
+        (output, _) => output,
+    };
+    // the generics of the handler can't be named by the generated impls, so
+    // responses that depend on them aren't inferred
+    let handler_generics = sig
+        .generics
+        .params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Type(param) => param.ident.clone(),
+            GenericParam::Const(param) => param.ident.clone(),
+            GenericParam::Lifetime(param) => param.lifetime.ident.clone(),
         })
         .collect::<Vec<_>>();
+    let output = output.filter(|output| !mentions(output.to_token_stream(), &handler_generics));
+
+    // the documentation is applied by the struct as a transform instead of
+    // as an extractor
+    let unreleased = doc.unreleased;
+    let docs = transform_only.then(|| {
+        quote! {
+            impl #struct_name {
+                /// Documents the operation, for `get_with` and the like, leaving
+                /// it out if it is newer than the version that the document is
+                /// generated for.
+                pub fn docs(mut transform: __aide::transform::TransformOperation<'_>) -> __aide::transform::TransformOperation<'_> {
+                    __aide::gen::in_context(|ctx| {
+                        <Self as __aide::OperationInput>::operation_input(ctx, transform.inner_mut());
+                    });
+                    transform.hidden(#unreleased)
+                }
+            }
+        }
+    });
+    let pending_responses = expand::pending_responses(output.as_ref());
+    let operation_input = expand::operation_input(&doc);
+    let summary = &doc.summary;
+    let description = &doc.description;
 
-    let doc_comment = doc_comments.join("\n");
-    let mut lines = doc_comment.lines().collect::<Vec<_>>();
+    let expanded = if let Some(ty) = attach {
+        quote! {
+            #cfg
+            const _: () = {
+                use #aide as __aide;
 
-    // separate summary from description
-    let first_empty_idx = lines
-        .iter()
-        .position(|line| line.trim().is_empty())
-        .unwrap_or(lines.len());
+                impl __aide::OperationInput for #ty {
+                    fn operation_input(_ctx: &mut __aide::gen::GenContext, operation: &mut __aide::openapi::Operation) {
+                        #pending_responses
+                        #error_responses
+                        #parameters
+                        #operation_input
+                        #inherit
+                        #use_const
+                        #summary_affixes
+                        #transform
+                    }
+                }
+            };
+        }
+    } else {
+        // the last argument is wrapped so that the documentation is applied
+        // after the other extractors have added their parameters
+        match (sig.inputs.last_mut(), owner) {
+            _ if transform_only => {}
+            // a function without a body can't have patterns in its arguments
+            (Some(FnArg::Typed(arg)), Owner::Trait { has_body, .. }) => {
+                let pat = &arg.pat;
+                let ty = &arg.ty;
+                if has_body {
+                    *arg.pat = parse_quote!(#struct_name(#pat, _));
+                }
+                *arg.ty = parse_quote!(#struct_name<#ty, Self>);
+            }
+            (Some(FnArg::Typed(arg)), Owner::Function(_)) => {
+                let pat = &arg.pat;
+                let ty = &arg.ty;
+                *arg.pat = parse_quote!(#struct_name(#pat));
+                *arg.ty = parse_quote!(#struct_name<#ty>);
+            }
+            (_, Owner::Trait { .. }) => sig.inputs.push(parse_quote!(_: #struct_name<(), Self>)),
+            (_, Owner::Function(_)) => sig.inputs.push(parse_quote!(_: #struct_name)),
+        }
+
+        // the implementations go in their own scope so that the crates can be
+        // named the same way wherever they come from
+        quote! {
+            #cfg
+            #vis struct #struct_name #params;
 
-    let summary = lines.drain(0..first_empty_idx).collect::<Vec<_>>().join("");
-    let summary = summary.trim();
+            #cfg
+            const _: () = {
+                use #aide as __aide;
+                use #axum as __axum;
 
-    let description = lines.join("\n");
-    let description = description.trim();
+                impl<T> #struct_name<T> {
+                    #[doc(hidden)]
+                    pub const SUMMARY: &'static str = #summary;
+                    #[doc(hidden)]
+                    pub const DESCRIPTION: &'static str = #description;
+                }
 
-    let struct_name = fn_def.sig.ident.to_string() + "_AideComment";
-    let struct_name = Ident::new(&struct_name, Span::mixed_site());
+                #methods
+                #docs
 
-    let vis = fn_def.vis.clone();
+                impl<T: __aide::OperationInput #bound> __aide::OperationInput for #struct_name #generics {
+                    fn operation_input(_ctx: &mut __aide::gen::GenContext, operation: &mut __aide::openapi::Operation) {
+                        T::operation_input(_ctx, operation);
+                        #pending_responses
+                        #error_responses
+                        #parameters
+                        #operation_input
+                        #implementation_doc
+                        #inherit
+                        #use_const
+                        #summary_affixes
+                        #transform
+                    }
 
-    let arg = syn::parse_str::<FnArg>(&format!("_: {struct_name}")).unwrap();
-    fn_def.sig.inputs.insert(0, arg);
+                    fn inferred_early_responses(
+                        ctx: &mut __aide::gen::GenContext,
+                        operation: &mut __aide::openapi::Operation,
+                    ) -> Vec<(Option<u16>, __aide::openapi::Response)> {
+                        T::inferred_early_responses(ctx, operation)
+                    }
+                }
+
+                #[__axum::async_trait]
+                impl<S, T #bound> __axum::extract::FromRequestParts<S> for #struct_name #generics
+                where
+                    S: Send + Sync,
+                    T: __axum::extract::FromRequestParts<S>,
+                {
+                    type Rejection = T::Rejection;
+                    async fn from_request_parts(
+                        parts: &mut __axum::http::request::Parts,
+                        state: &S,
+                    ) -> Result<Self, Self::Rejection> {
+                        T::from_request_parts(parts, state).await.map(#wrap)
+                    }
+                }
+
+                #[__axum::async_trait]
+                impl<S, T #bound> __axum::extract::FromRequest<S> for #struct_name #generics
+                where
+                    S: Send + Sync,
+                    T: __axum::extract::FromRequest<S>,
+                {
+                    type Rejection = T::Rejection;
+                    async fn from_request(
+                        req: __axum::extract::Request,
+                        state: &S,
+                    ) -> Result<Self, Self::Rejection> {
+                        T::from_request(req, state).await.map(#wrap)
+                    }
+                }
+            };
+        }
+    };
+
+    match debug {
+        Some(signature) => {
+            let message = format!(
+                "`#[aidecomment(debug)]` on `{signature}`\n\nsummary: {summary:?}\ndescription: {description:?}\n\n{expanded}"
+            );
+            let warning = debug_warning(&message, sig.ident.span());
+            Ok(quote! {
+                #expanded
+                #warning
+            })
+        }
+        None => Ok(expanded),
+    }
+}
 
+/// A warning with a message, which is the deprecation note of an item that is
+/// used where the handler is, since proc macros can't emit warnings on
+/// stable.
+fn debug_warning(message: &str, span: Span) -> proc_macro2::TokenStream {
+    let name = Ident::new("AideCommentDebug", Span::mixed_site());
+    let used = quote::quote_spanned!(span=> #name);
     quote! {
-        #vis struct #struct_name;
+        const _: () = {
+            #[deprecated = #message]
+            struct #name;
+            let _ = #used;
+        };
+    }
+}
 
-        impl ::aide::OperationInput for #struct_name {
-            fn operation_input(_ctx: &mut ::aide::gen::GenContext, operation: &mut ::aide::openapi::Operation) {
-                operation.summary = Some(#summary.to_owned());
-                operation.description = Some(#description.to_owned());
-            }
+/// The path of the struct generated for a handler, given the path of the
+/// handler.
+fn struct_path(mut handler: syn::Path) -> syn::Path {
+    // the struct of an associated function is next to its impl block instead
+    // of in the type, so the type is left out
+    let len = handler.segments.len();
+    if len >= 2 {
+        let ty = &handler.segments[len - 2].ident;
+        if ty == "Self" || ty.unraw().to_string().starts_with(char::is_uppercase) {
+            handler.segments = handler
+                .segments
+                .iter()
+                .take(len - 2)
+                .chain(handler.segments.last())
+                .cloned()
+                .collect();
         }
+    }
+    if let Some(segment) = handler.segments.last_mut() {
+        segment.ident = Ident::new(
+            &format!("{}_AideComment", segment.ident.unraw()),
+            segment.ident.span(),
+        );
+        segment.arguments = syn::PathArguments::None;
+    }
+    handler
+}
+
+/// Whether a function is certainly an associated function, because it takes
+/// `self` or mentions `Self`.
+fn is_associated(sig: &Signature) -> bool {
+    let self_ty = [Ident::new("Self", Span::call_site())];
+    sig.receiver().is_some()
+        || mentions(sig.inputs.to_token_stream(), &self_ty)
+        || mentions(sig.output.to_token_stream(), &self_ty)
+}
+
+/// Whether any of the identifiers are in the tokens.
+fn mentions(tokens: proc_macro2::TokenStream, idents: &[Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => idents.contains(&ident),
+        TokenTree::Group(group) => mentions(group.stream(), idents),
+        _ => false,
+    })
+}
 
-        #[::axum::async_trait]
-        impl<S> ::axum::extract::FromRequestParts<S> for #struct_name {
-            type Rejection = ::std::convert::Infallible;
-            async fn from_request_parts(
-                _parts: &mut ::axum::http::request::Parts,
-                _state: &S,
-            ) -> Result<Self, Self::Rejection> {
-                Ok(#struct_name)
+/// Replaces `Self` with the type it stands for.
+fn replace_self(tokens: proc_macro2::TokenStream, self_ty: &Type) -> proc_macro2::TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(ident) if ident == "Self" => self_ty.to_token_stream(),
+            TokenTree::Group(group) => {
+                let mut replaced =
+                    Group::new(group.delimiter(), replace_self(group.stream(), self_ty));
+                replaced.set_span(group.span());
+                TokenTree::Group(replaced).into()
             }
+            token => token.into(),
+        })
+        .collect()
+}
+
+/// Documents what a middleware requires of the operations that it wraps, like
+/// a header that every request needs, with its doc comment.
+///
+/// The doc comment is added as a paragraph to the end of the descriptions of
+/// the operations. Since aide doesn't know which routes a layer wraps, a
+/// `{middleware}_AideComment` struct has a `path_item` function to pass to
+/// `with_path_items` on the router that the layer wraps, and a `docs`
+/// function to pass to `get_with` and the like for single routes.
+///
+/// ```
+/// # use aidecomment::{aidecomment, aidecomment_middleware};
+/// use aide::axum::{ApiRouter, routing::get};
+/// use axum::{extract::Request, middleware::{self, Next}, response::Response};
+///
+/// /// Requires the `X-Tenant` header, which says which tenant the request is for.
+/// #[aidecomment_middleware]
+/// async fn require_tenant(request: Request, next: Next) -> Response {
+///     next.run(request).await
+/// }
+///
+/// /// Lists users
+/// #[aidecomment]
+/// async fn list_users() {}
+///
+/// let router = ApiRouter::<()>::new()
+///     .api_route("/", get(list_users))
+///     .with_path_items(require_tenant_AideComment::path_item)
+///     .layer(middleware::from_fn(require_tenant));
+/// ```
+#[proc_macro_attribute]
+pub fn aidecomment_middleware(attr: TokenStream, item: TokenStream) -> TokenStream {
+    with_tracked_files(middleware::expand(attr.into(), item.into()))
+}
+
+/// Documents a closure handler the way [`macro@aidecomment`] documents a
+/// function.
+///
+/// The doc comment goes before the closure, along with an
+/// `#[aidecomment(...)]` attribute for any arguments. They all work the same
+/// way except for `cfg`, since there is no other closure to use instead.
+///
+/// ```
+/// # use aidecomment::aidecomment_closure;
+/// use aide::axum::{ApiRouter, routing::get};
+/// use axum::extract::Path;
+///
+/// let router = ApiRouter::<()>::new().api_route(
+///     "/:id",
+///     get(aidecomment_closure! {
+///         /// Gets a user
+///         #[aidecomment(tag = "users")]
+///         |Path(id): Path<u64>| async move { id.to_string() }
+///     }),
+/// );
+/// ```
+#[proc_macro]
+pub fn aidecomment_closure(input: TokenStream) -> TokenStream {
+    expression_with_tracked_files(closure_handler(input.into()))
+}
+
+/// Wraps the last argument of a closure, with the generated code in a block
+/// along with it.
+fn closure_handler(input: proc_macro2::TokenStream) -> syn::Result<proc_macro2::TokenStream> {
+    let parser = |input: ParseStream| {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let closure = input.parse::<ExprClosure>()?;
+        Ok((attrs, closure))
+    };
+    let (mut attrs, mut closure) = parser.parse2(input)?;
+
+    // the closure is documented like a function with the same arguments
+    let inputs = closure.inputs.iter().map(|pat| match pat {
+        Pat::Type(pat) => pat.clone(),
+        pat => parse_quote!(#pat: _),
+    });
+    let output = &closure.output;
+    let mut sig: Signature = parse_quote!(fn closure(#(#inputs),*) #output);
+
+    let args =
+        own_args(&proc_macro2::TokenStream::new(), &mut attrs, &sig, false)?.unwrap_or_default();
+    let unsupported = [
+        ("cfg", args.cfg.is_some()),
+        ("transform_only", args.transform_only),
+    ];
+    if let Some((name, _)) = unsupported.iter().find(|(_, is_set)| *is_set) {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            format!("`{name}` can't be used with a closure"),
+        ));
+    }
+
+    let items = handler(
+        args,
+        &attrs,
+        &Visibility::Inherited,
+        &mut sig,
+        Owner::Function(None),
+    )?;
+    closure.inputs = sig
+        .inputs
+        .into_iter()
+        .filter_map(|arg| match arg {
+            FnArg::Typed(arg) => Some(Pat::Type(arg)),
+            FnArg::Receiver(_) => None,
+        })
+        .collect();
+
+    Ok(quote! {
+        {
+            #items
+            #closure
         }
+    })
+}
+
+/// Builds an aide `ApiRouter` from a table of routes, each of which is
+/// documented where it is declared instead of on its handler.
+///
+/// A route is a method, a path, and a handler, like
+/// `GET "/users/{id}" => get_user`. The doc comment before it is parsed the
+/// same way [`macro@aidecomment`] parses the doc comment of a handler, along
+/// with an `#[aidecomment(...)]` attribute for any arguments. The routes of
+/// the same path are routed together, and paths can be written the way they
+/// are in the document or the way axum expects them, like `"/users/:id"`.
+///
+/// ```
+/// # use aidecomment::aidecomment_routes;
+/// use axum::extract::Path;
+///
+/// async fn get_user(Path(id): Path<u64>) -> String {
+///     id.to_string()
+/// }
+///
+/// async fn delete_user(Path(_id): Path<u64>) {}
+///
+/// let router: aide::axum::ApiRouter = aidecomment_routes! {
+///     /// Gets a user
+///     ///
+///     /// The user is looked up by their id.
+///     #[aidecomment(tag = "users")]
+///     GET "/users/{id}" => get_user,
+///     /// Deletes a user
+///     DELETE "/users/{id}" => delete_user,
+/// };
+/// ```
+///
+/// The arguments that are for a single handler work the same way, except for
+/// `struct_name`, `attach`, `method`, and `cfg`, since the macro decides how
+/// the routes are documented.
+#[proc_macro]
+pub fn aidecomment_routes(input: TokenStream) -> TokenStream {
+    expression_with_tracked_files(routes::expand(input.into()))
+}
+
+/// Routes handlers that are documented with `transform_only` along with the
+/// transforms of their documentation, for codebases that use aide's
+/// `get_with` instead of extractors.
+///
+/// `documented!(get get_user)` is `get_with(get_user, get_user_AideComment::docs)`,
+/// and more methods can be routed after a comma, like
+/// `documented!(get get_user, delete delete_user)`. The methods can be written
+/// in uppercase too, like in [`aidecomment_routes!`].
+///
+/// ```
+/// # use aidecomment::{aidecomment, documented};
+/// use aide::axum::ApiRouter;
+///
+/// /// Gets a user
+/// #[aidecomment(transform_only)]
+/// async fn get_user() {}
+///
+/// /// Deletes a user
+/// #[aidecomment(transform_only, tag = "admin")]
+/// async fn delete_user() {}
+///
+/// let router = ApiRouter::<()>::new().api_route("/", documented!(get get_user, delete delete_user));
+/// ```
+#[proc_macro]
+pub fn documented(input: TokenStream) -> TokenStream {
+    expression_with_tracked_files(routes::documented(input.into()))
+}
+
+/// Declares the tags of the document, each of which is described by the doc
+/// comment of a unit struct that stands for it.
+///
+/// The name of the tag is the name of the struct in snake case, unless
+/// `#[aidecomment(name = "...")]` names it otherwise, and
+/// `external_docs(url = "...", description = "...")` links to further
+/// documentation. The struct has the `NAME` of the tag for the handlers that
+/// are tagged with it, a `tag` function that returns the aide `Tag`, and a
+/// `register` function that adds it to the document for `finish_api_with`.
+///
+/// ```
+/// # use aidecomment::{aidecomment, aidecomment_tag};
+/// aidecomment_tag! {
+///     /// Everything about the users of the service
+///     pub struct Users;
+///
+///     /// Operations for administrators
+///     #[aidecomment(name = "admin", external_docs(url = "https://example.com/admin"))]
+///     pub struct Administration;
+/// }
+///
+/// /// Gets a user
+/// #[aidecomment(tag = "users")]
+/// async fn get_user() {}
+///
+/// # use aide::{axum::{ApiRouter, routing::get}, openapi::OpenApi};
+/// let mut api = OpenApi::default();
+/// ApiRouter::<()>::new()
+///     .api_route("/", get(get_user))
+///     .finish_api_with(&mut api, |api| Administration::register(Users::register(api)));
+/// ```
+#[proc_macro]
+pub fn aidecomment_tag(input: TokenStream) -> TokenStream {
+    with_tracked_files(tag::expand(input.into()))
+}
 
-        #fn_def
-    }.into()
+/// Describes the document with the doc comment of the crate and the metadata
+/// of its package, as an aide `Info`.
+///
+/// The first paragraph of the doc comment is the title, which can be written
+/// as a heading, and the rest is the description. Without a doc comment,
+/// they are the name and description of the package instead. The version is
+/// always the version of the package.
+///
+/// The crate root is `src/lib.rs` or `src/main.rs`, unless another one is
+/// given relative to the package, like `aidecomment_info!("src/bin/server.rs")`.
+///
+/// ```
+/// # use aidecomment::aidecomment_info;
+/// # use aide::{axum::ApiRouter, openapi::OpenApi};
+/// let mut api = OpenApi {
+///     info: aidecomment_info!(),
+///     ..OpenApi::default()
+/// };
+/// ```
+#[proc_macro]
+pub fn aidecomment_info(input: TokenStream) -> TokenStream {
+    expression_with_tracked_files(info::expand(input.into()))
+}
+
+/// A derive for error enums that documents their responses for the `error`
+/// argument of [`macro@aidecomment`].
+///
+/// Every variant needs a `#[status(...)]` attribute with the status code it
+/// responds with, and its doc comment describes the response. Variants with
+/// the same status are listed in the description of one response.
+///
+/// ```
+/// # use aidecomment::ErrorResponses;
+/// #[derive(ErrorResponses)]
+/// enum PaymentError {
+///     /// The card was declined
+///     #[status(402)]
+///     Declined,
+///     /// The card has expired
+///     #[status(402)]
+///     Expired,
+/// }
+/// ```
+#[proc_macro_derive(ErrorResponses, attributes(status))]
+pub fn error_responses(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    with_tracked_files(errors::derive(&input))
+}
+
+/// A derive for error enums that documents their responses as their aide
+/// `OperationOutput`, so that handlers returning them in a `Result` have them
+/// documented without any arguments.
+///
+/// Like with [`ErrorResponses`], every variant needs a `#[status(...)]`
+/// attribute and its doc comment describes the response. The type still needs
+/// to implement `IntoResponse` itself.
+///
+/// ```
+/// # use aidecomment::{aidecomment, AideCommentResponses};
+/// # use axum::{http::StatusCode, response::{IntoResponse, Response}};
+/// #[derive(AideCommentResponses)]
+/// enum OrderError {
+///     /// The order doesn't exist
+///     #[status(404)]
+///     NotFound,
+///     /// The order was already shipped
+///     #[status(409)]
+///     Shipped,
+/// }
+/// # impl IntoResponse for OrderError {
+/// #     fn into_response(self) -> Response { StatusCode::NOT_FOUND.into_response() }
+/// # }
+///
+/// /// Cancels an order
+/// #[aidecomment]
+/// async fn cancel_order() -> Result<(), OrderError> {
+///     Err(OrderError::Shipped)
+/// }
+/// ```
+#[proc_macro_derive(AideCommentResponses, attributes(status))]
+pub fn aidecomment_responses(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    with_tracked_files(errors::derive_output(&input))
+}
+
+/// A derive for wrapper types that are responded with, which describes their
+/// response with their doc comment.
+///
+/// The type needs to have one field, which it is documented like, and to
+/// implement `IntoResponse` itself. A `#[status(...)]` attribute replaces the
+/// status that the field responds with.
+///
+/// ```
+/// # use aidecomment::{aidecomment, AideCommentResponse};
+/// use axum::{http::StatusCode, response::{IntoResponse, Response}, Json};
+///
+/// /// The resource was created
+/// #[derive(AideCommentResponse)]
+/// #[status(201)]
+/// struct Created<T>(Json<T>);
+///
+/// impl<T: serde::Serialize> IntoResponse for Created<T> {
+///     fn into_response(self) -> Response {
+///         (StatusCode::CREATED, self.0).into_response()
+///     }
+/// }
+///
+/// /// Creates a user
+/// #[aidecomment]
+/// async fn create_user() -> Created<String> {
+///     Created(Json("alice".to_owned()))
+/// }
+/// ```
+#[proc_macro_derive(AideCommentResponse, attributes(status))]
+pub fn aidecomment_response(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    with_tracked_files(response::derive(&input))
+}
+
+/// A derive for structs that stand for a security scheme, which is described
+/// by their doc comment.
+///
+/// The `#[scheme(...)]` attribute says what kind of scheme it is, which is
+/// one of:
+///
+/// - `bearer`, or `bearer(format = "JWT")` with the format of the token
+/// - `basic`
+/// - `api_key(header = "...")`, `api_key(query = "...")`, or
+///   `api_key(cookie = "...")` with the name of the key
+/// - `openid_connect(url = "...")`
+/// - `mutual_tls`
+///
+/// The name of the scheme is the name of the struct in snake case, unless
+/// `name = "..."` names it otherwise, so that `BearerAuth` is the scheme
+/// that `auth = "bearer"` requires. The struct has the `NAME` of the scheme,
+/// a `security_scheme` function that returns the aide `SecurityScheme`, and
+/// a `register` function that adds it to the document for `finish_api_with`.
+///
+/// ```
+/// # use aidecomment::AideCommentSecurityScheme;
+/// /// Bearer JWT issued by /login
+/// #[derive(AideCommentSecurityScheme)]
+/// #[scheme(bearer(format = "JWT"))]
+/// struct BearerAuth;
+///
+/// /// A key for scripts, which is created in the settings
+/// #[derive(AideCommentSecurityScheme)]
+/// #[scheme(api_key(header = "X-API-Key"), name = "api_key")]
+/// struct ScriptKey;
+///
+/// # use aide::{axum::ApiRouter, openapi::OpenApi};
+/// let mut api = OpenApi::default();
+/// ApiRouter::<()>::new().finish_api_with(&mut api, |api| ScriptKey::register(BearerAuth::register(api)));
+/// ```
+#[proc_macro_derive(AideCommentSecurityScheme, attributes(scheme))]
+pub fn aidecomment_security_scheme(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    with_tracked_files(security::derive(&input))
+}
+
+/// A derive for structs that are extracted with `Query` or `Path`, which
+/// documents their parameters for the `params` argument of
+/// [`macro@aidecomment`].
+///
+/// The doc comment of each field describes the parameter with its name, or
+/// with the name from `#[serde(rename = "...")]`.
+///
+/// ```
+/// # use aidecomment::AideCommentParams;
+/// # use serde::Deserialize;
+/// #[derive(Deserialize, AideCommentParams)]
+/// struct Pagination {
+///     /// The page to get, starting at 1
+///     page: u32,
+///     /// How many items are on a page
+///     #[serde(rename = "perPage")]
+///     per_page: u32,
+/// }
+/// ```
+#[proc_macro_derive(AideCommentParams)]
+pub fn aidecomment_params(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    with_tracked_files(params::derive(&input))
 }
@@ -0,0 +1,89 @@
+//! The `aidecomment_middleware` attribute, which documents what a middleware
+//! requires of every operation that it wraps.
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::ItemFn;
+
+use crate::args::Args;
+use crate::generated_ident;
+use crate::tag;
+
+/// Generates a struct for the middleware whose functions add its doc comment
+/// to the descriptions of operations.
+pub(crate) fn expand(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    if !attr.is_empty() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "`aidecomment_middleware` doesn't take any arguments",
+        ));
+    }
+    let middleware = syn::parse2::<ItemFn>(item)?;
+
+    let paragraph = tag::description(&middleware.attrs)?;
+    if paragraph.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &middleware.sig.ident,
+            "the middleware needs a doc comment, which is added to the descriptions of the operations it wraps",
+        ));
+    }
+
+    let aide = Args::default().crate_path("aide")?;
+    let vis = &middleware.vis;
+    let struct_name = generated_ident(
+        &format!("{}_AideComment", middleware.sig.ident.unraw()),
+        &middleware.sig.ident,
+    );
+
+    let mut expanded = middleware.to_token_stream();
+    expanded.extend(quote! {
+        #vis struct #struct_name;
+
+        const _: () = {
+            use #aide as __aide;
+
+            impl #struct_name {
+                /// The paragraph that is added to the descriptions.
+                pub const DESCRIPTION: &'static str = #paragraph;
+
+                /// Adds the paragraph to the description of an operation, for
+                /// `get_with` and the like.
+                pub fn docs(mut transform: __aide::transform::TransformOperation<'_>) -> __aide::transform::TransformOperation<'_> {
+                    let operation = transform.inner_mut();
+                    let description = operation.description.take().unwrap_or_default();
+                    // the middleware may wrap a router that is already
+                    // documented with it
+                    operation.description = Some(if description.contains(Self::DESCRIPTION) {
+                        description
+                    } else {
+                        format!("{}\n\n{}", description, Self::DESCRIPTION).trim().to_owned()
+                    });
+                    transform
+                }
+
+                /// Adds the paragraph to the descriptions of every operation
+                /// of a path item, for `with_path_items` and `api_route_with`.
+                pub fn path_item(mut path_item: __aide::transform::TransformPathItem<'_>) -> __aide::transform::TransformPathItem<'_> {
+                    let item = path_item.inner_mut();
+                    let operations = [
+                        &mut item.get,
+                        &mut item.put,
+                        &mut item.post,
+                        &mut item.delete,
+                        &mut item.options,
+                        &mut item.head,
+                        &mut item.patch,
+                        &mut item.trace,
+                    ];
+                    for operation in operations.into_iter().flatten() {
+                        let _ = Self::docs(__aide::transform::TransformOperation::new(operation));
+                    }
+                    path_item
+                }
+            }
+        };
+    });
+
+    Ok(expanded)
+}
@@ -0,0 +1,1948 @@
+//! The documentation gathered for an operation, independent of where it came
+//! from.
+
+use proc_macro2::Span;
+
+use serde_json::Value;
+
+use crate::args::METHODS;
+use crate::comment::{self, Directive, DocComment, FrontMatter, Line, Section, Tag};
+use crate::{config, yaml};
+
+/// The documentation to apply to an aide `Operation`.
+#[derive(Default)]
+pub(crate) struct OperationDoc {
+    pub summary: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    pub operation_id: Option<String>,
+    pub responses: Vec<ResponseDoc>,
+    /// The description of the successful response.
+    pub returns: Option<String>,
+    pub response_headers: Vec<ResponseHeaderDoc>,
+    pub parameters: Vec<ParameterDoc>,
+    pub request_body: Option<String>,
+    /// The fields of a `multipart/form-data` request body.
+    pub upload_fields: Vec<UploadFieldDoc>,
+    pub examples: Vec<ExampleDoc>,
+    /// The media types that replace the inferred ones of the request body.
+    pub consumes: Vec<String>,
+    /// The media types that replace the inferred ones of the successful
+    /// responses.
+    pub produces: Vec<String>,
+    /// Alternative security requirements, each of which lists the schemes
+    /// and their scopes that are needed together.
+    pub security: Vec<Vec<(String, Vec<String>)>>,
+    /// Scopes needed for every scheme of the security requirements.
+    pub scopes: Vec<String>,
+    /// The URL of further documentation along with its description.
+    pub external_docs: Option<(String, Option<String>)>,
+    /// Alternative base URLs along with their descriptions.
+    pub servers: Vec<(String, Option<String>)>,
+    /// Requests the API makes in response to the operation.
+    pub callbacks: Vec<CallbackDoc>,
+    /// Why the operation is deprecated, which may be empty, if it is.
+    pub deprecated: Option<String>,
+    pub rate_limits: Vec<RateLimitDoc>,
+    /// The largest request body that is accepted, in bytes and as written.
+    pub max_request_size: Option<(u64, String)>,
+    /// Describes how the results are paginated, if they are.
+    pub pagination: Option<String>,
+    /// The version of the API the operation was added in.
+    pub since: Option<String>,
+    /// Whether the operation is newer than the version the documentation is
+    /// generated for.
+    pub unreleased: bool,
+    /// The protocol the connection switches to, if it isn't plain HTTP.
+    pub protocol: Option<Protocol>,
+    /// Vendor extensions, keyed with their `x-` prefix.
+    pub extensions: Vec<(String, serde_json::Value)>,
+    /// How the summary and description are combined with the ones that the
+    /// extractors set.
+    pub merge: Merge,
+    /// Whether the summary is left as it is instead of being set.
+    pub omit_summary: bool,
+    /// Whether the description is left as it is instead of being set.
+    pub omit_description: bool,
+    /// The case and prefix of a tag named after the module of the handler,
+    /// if it is tagged with one.
+    pub module_tag: Option<(Case, String)>,
+}
+
+/// How a summary or description is combined with an existing one.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum Merge {
+    #[default]
+    Replace,
+    /// Adds to the end of the existing one.
+    Append,
+    /// Only sets it if there isn't an existing one.
+    Fill,
+}
+
+impl Merge {
+    pub(crate) fn parse(text: &str) -> Option<Merge> {
+        match text {
+            "replace" => Some(Merge::Replace),
+            "append" => Some(Merge::Append),
+            "fill" | "fill_if_empty" => Some(Merge::Fill),
+            _ => None,
+        }
+    }
+}
+
+/// How the name of a module is written in a tag.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum Case {
+    /// As it is, like `user_accounts`.
+    #[default]
+    Snake,
+    /// Like `user-accounts`.
+    Kebab,
+    /// Like `User Accounts`.
+    Title,
+    /// Like `UserAccounts`.
+    Pascal,
+}
+
+impl Case {
+    pub(crate) fn parse(text: &str) -> Option<Case> {
+        match text {
+            "snake" => Some(Case::Snake),
+            "kebab" => Some(Case::Kebab),
+            "title" => Some(Case::Title),
+            "pascal" => Some(Case::Pascal),
+            _ => None,
+        }
+    }
+}
+
+/// A protocol that an operation uses instead of a plain request and response.
+#[derive(Clone, Copy)]
+pub(crate) enum Protocol {
+    WebSocket,
+    ServerSentEvents,
+}
+
+impl Protocol {
+    fn parse(text: &str) -> Option<Protocol> {
+        match text.to_lowercase().replace([' ', '-', '_'], "").as_str() {
+            "websocket" | "websockets" | "ws" => Some(Protocol::WebSocket),
+            "sse" | "serversentevents" | "eventstream" => Some(Protocol::ServerSentEvents),
+            _ => None,
+        }
+    }
+
+    /// The value of the `x-protocol` extension.
+    fn name(self) -> &'static str {
+        match self {
+            Protocol::WebSocket => "websocket",
+            Protocol::ServerSentEvents => "sse",
+        }
+    }
+
+    /// How it is shown after the summary.
+    fn label(self) -> &'static str {
+        match self {
+            Protocol::WebSocket => "WebSocket",
+            Protocol::ServerSentEvents => "SSE",
+        }
+    }
+
+    /// Explains how the connection behaves, for the description.
+    fn semantics(self) -> &'static str {
+        match self {
+            Protocol::WebSocket => {
+                "This endpoint upgrades the connection to a WebSocket. The request \
+                 needs the `Connection: Upgrade` and `Upgrade: websocket` headers, and \
+                 the server responds with `101 Switching Protocols` before messages \
+                 are exchanged in both directions."
+            }
+            Protocol::ServerSentEvents => {
+                "This endpoint responds with a stream of server-sent events. The \
+                 response has the `text/event-stream` content type and is kept open \
+                 while events are sent."
+            }
+        }
+    }
+}
+
+/// A documented response of an operation.
+pub(crate) struct ResponseDoc {
+    pub status: Status,
+    pub description: String,
+}
+
+/// A header that is set on a response.
+pub(crate) struct ResponseHeaderDoc {
+    /// The response it is set on, or the successful response if `None`.
+    pub status: Option<Status>,
+    pub name: String,
+    pub description: String,
+    pub required: Option<bool>,
+    pub example: Option<serde_json::Value>,
+}
+
+/// The documentation for a parameter of an operation, matched by name.
+pub(crate) struct ParameterDoc {
+    pub name: String,
+    pub description: String,
+    /// Where the parameter is, if it should only match parameters there.
+    pub location: Option<Location>,
+    pub required: Option<bool>,
+    /// The allowed values, from a description like ``one of `a`, `b` ``.
+    pub enum_values: Vec<serde_json::Value>,
+    /// An example value, from an `(e.g. 42)` in the description.
+    pub example: Option<serde_json::Value>,
+    /// Constraints, from a `(min 1, max 100)` in the description.
+    pub minimum: Option<f64>,
+    pub maximum: Option<f64>,
+    pub pattern: Option<String>,
+    /// Whether the parameter is added if it doesn't exist.
+    pub add_if_missing: bool,
+    /// The type of the parameter if it is added.
+    pub schema_type: SchemaType,
+}
+
+/// The type of a parameter or form field that is added by the documentation.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SchemaType {
+    String,
+    Integer,
+    Number,
+    Boolean,
+    /// A string with the `binary` format, like an uploaded file.
+    Binary,
+}
+
+impl SchemaType {
+    fn parse(text: &str) -> Option<SchemaType> {
+        match text.to_lowercase().as_str() {
+            "string" | "text" => Some(SchemaType::String),
+            "integer" | "int" => Some(SchemaType::Integer),
+            "number" | "float" => Some(SchemaType::Number),
+            "boolean" | "bool" => Some(SchemaType::Boolean),
+            "binary" | "file" => Some(SchemaType::Binary),
+            _ => None,
+        }
+    }
+}
+
+/// A field of a `multipart/form-data` request body.
+pub(crate) struct UploadFieldDoc {
+    pub name: String,
+    pub description: String,
+    /// The type of the field if it isn't in the schema already.
+    pub schema_type: SchemaType,
+    pub required: bool,
+}
+
+impl ParameterDoc {
+    /// Creates the parameter, reading the flags and constraints that its
+    /// description can contain.
+    pub(crate) fn parse(
+        name: &str,
+        description: &str,
+        location: Option<Location>,
+        span: Span,
+    ) -> syn::Result<ParameterDoc> {
+        let (description, example) = example_value(description);
+        let (description, required) = required_flag(&description);
+        let enum_values = allowed_values(description);
+
+        let mut parameter = ParameterDoc {
+            name: name.to_owned(),
+            description: description.to_owned(),
+            location,
+            required,
+            enum_values,
+            example,
+            minimum: None,
+            maximum: None,
+            pattern: None,
+            add_if_missing: location.is_some_and(Location::is_added_if_missing),
+            schema_type: SchemaType::String,
+        };
+        parameter.parse_constraints(span)?;
+
+        Ok(parameter)
+    }
+
+    /// Finds constraints like `(min 1, max 100)` or ``(pattern `^\w+$`)`` in
+    /// the description.
+    fn parse_constraints(&mut self, span: Span) -> syn::Result<()> {
+        let description = self.description.clone();
+        let groups = description
+            .split('(')
+            .skip(1)
+            .filter_map(|group| group.split_once(')'))
+            .map(|(group, _)| group);
+
+        for group in groups {
+            let constraints = group
+                .split(',')
+                .map(|constraint| constraint.trim().split_once(' '))
+                .collect::<Option<Vec<_>>>();
+
+            let Some(constraints) = constraints else {
+                continue;
+            };
+            if !constraints
+                .iter()
+                .all(|(kind, _)| ["min", "max", "pattern"].contains(kind))
+            {
+                continue;
+            }
+
+            for (kind, value) in constraints {
+                let value = value.trim();
+                let number = || {
+                    value.parse::<f64>().map_err(|_| {
+                        syn::Error::new(span, format!("invalid `{kind}` constraint `{value}`"))
+                    })
+                };
+
+                match kind {
+                    "min" => self.minimum = Some(number()?),
+                    "max" => self.maximum = Some(number()?),
+                    _ => {
+                        let pattern = value
+                            .strip_prefix('`')
+                            .and_then(|value| value.strip_suffix('`'))
+                            .unwrap_or(value);
+                        self.pattern = Some(pattern.to_owned());
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A request the API makes in response to an operation, like a webhook.
+pub(crate) struct CallbackDoc {
+    pub name: String,
+    /// The lowercase HTTP method, which is a valid `PathItem` field.
+    pub method: String,
+    /// The runtime expression of the URL, like `{$request.body#/callbackUrl}`.
+    pub url: String,
+    pub description: String,
+}
+
+/// A limit on how often an operation can be called, like `100 requests/minute
+/// per token`.
+pub(crate) struct RateLimitDoc {
+    pub limit: u64,
+    /// The period as a singular word, like `minute`.
+    pub period: String,
+    /// What the limit applies to, like `token` or `IP address`.
+    pub scope: Option<String>,
+}
+
+impl RateLimitDoc {
+    /// Parses `100 requests/minute`, `100 requests per minute`, or `100/min`
+    /// followed by an optional `per scope`.
+    fn parse(text: &str) -> Option<RateLimitDoc> {
+        let (limit, rest) = text.split_once(|c: char| c.is_whitespace() || c == '/')?;
+        let limit = limit.parse().ok()?;
+
+        let rest = rest.trim_start();
+        let rest = ["requests", "request", "calls", "call"]
+            .into_iter()
+            .find_map(|unit| rest.strip_prefix(unit))
+            .unwrap_or(rest)
+            .trim_start();
+        let rest = rest
+            .strip_prefix('/')
+            .or_else(|| rest.strip_prefix("per "))
+            .or_else(|| rest.strip_prefix("a "))
+            .unwrap_or(rest)
+            .trim_start();
+
+        let (period, scope) = rest.split_once(' ').unwrap_or((rest, ""));
+        let period = match period.trim_end_matches(['.', ',']) {
+            "s" | "sec" | "second" => "second",
+            "m" | "min" | "minute" => "minute",
+            "h" | "hr" | "hour" => "hour",
+            "d" | "day" => "day",
+            _ => return None,
+        };
+
+        let scope = scope.trim().trim_end_matches('.');
+        let scope = scope.strip_prefix("per ").unwrap_or(scope).trim();
+
+        Some(RateLimitDoc {
+            limit,
+            period: period.to_owned(),
+            scope: (!scope.is_empty()).then(|| scope.to_owned()),
+        })
+    }
+
+    /// The limit written out in a standard way.
+    fn describe(&self) -> String {
+        let requests = if self.limit == 1 {
+            "request"
+        } else {
+            "requests"
+        };
+        match &self.scope {
+            Some(scope) => format!("{} {requests} per {} per {scope}", self.limit, self.period),
+            None => format!("{} {requests} per {}", self.limit, self.period),
+        }
+    }
+}
+
+/// The location of a parameter.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Location {
+    Query,
+    Path,
+    Header,
+    Cookie,
+}
+
+impl Location {
+    /// Whether a documented parameter is added if it doesn't exist, since
+    /// these are commonly read without an extractor that documents them.
+    pub(crate) fn is_added_if_missing(self) -> bool {
+        matches!(self, Location::Header | Location::Cookie)
+    }
+}
+
+/// An example value for the request body or a response.
+pub(crate) struct ExampleDoc {
+    pub target: ExampleTarget,
+    /// The name of the example if it is one of several.
+    pub name: Option<String>,
+    pub media_type: &'static str,
+    /// The example as compact JSON.
+    pub value: String,
+}
+
+/// Where an example should be attached to.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ExampleTarget {
+    /// The request body if there is one, the successful response otherwise.
+    Auto,
+    RequestBody,
+    /// The successful response.
+    Response,
+    Status(Status),
+}
+
+/// The key of a response in an OpenAPI `responses` object.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Status {
+    Default,
+    Code(u16),
+    Range(u16),
+}
+
+impl Status {
+    /// Parses a status code like `404`, a range like `4XX`, or `default`.
+    pub(crate) fn parse(text: &str) -> Option<Status> {
+        if text.eq_ignore_ascii_case("default") {
+            return Some(Status::Default);
+        }
+
+        if let Some(class) = text.strip_suffix("XX").or_else(|| text.strip_suffix("xx")) {
+            return match class.parse::<u16>() {
+                Ok(class @ 1..=5) => Some(Status::Range(class)),
+                _ => None,
+            };
+        }
+
+        match text.parse::<u16>() {
+            Ok(code @ 100..=599) => Some(Status::Code(code)),
+            _ => None,
+        }
+    }
+}
+
+impl OperationDoc {
+    pub(crate) fn from_comment(doc: DocComment) -> syn::Result<OperationDoc> {
+        let mut operation = OperationDoc::default();
+        let mut remaining = doc.body;
+
+        for directive in &doc.directives {
+            operation.apply_directive(directive)?;
+        }
+        for tag in &doc.tags {
+            operation.apply_tag(tag)?;
+        }
+
+        let stripped = config::stripped_sections();
+
+        for section in doc.sections {
+            let rest = if section.is("Errors") || section.is("Responses") {
+                operation.parse_responses(&section.lines)?
+            } else if section.is("Response Headers") {
+                operation.parse_response_headers(&section.lines)?
+            } else if section.is("Returns") {
+                operation.returns = Some(comment::join(&section.lines));
+                Vec::new()
+            } else if section.is("Upload") {
+                operation.parse_upload(&section.lines)?
+            } else if section.is("Request Body") || section.is("Body") {
+                operation.parse_request_body(&section.lines)?
+            } else if section.is("Examples") {
+                operation.parse_examples(&section.lines)?
+            } else if section.is("Parameters") || section.is("Arguments") {
+                operation.parse_parameters(&section.lines, None)?
+            } else if section.is("Query") {
+                operation.parse_parameters(&section.lines, Some(Location::Query))?
+            } else if section.is("Path") {
+                operation.parse_parameters(&section.lines, Some(Location::Path))?
+            } else if section.is("Headers") {
+                operation.parse_parameters(&section.lines, Some(Location::Header))?
+            } else if section.is("Cookies") {
+                operation.parse_parameters(&section.lines, Some(Location::Cookie))?
+            } else if section.is("Security") {
+                operation.parse_security(&section.lines)?
+            } else if section.is("Scopes") {
+                operation.parse_scopes(&section.lines)?
+            } else if section.is("Servers") {
+                operation.parse_servers(&section.lines)?
+            } else if section.is("Callbacks") || section.is("Webhooks") {
+                operation.parse_callbacks(&section.lines)?
+            } else if section.is("Deprecated") {
+                operation.deprecated = Some(comment::join(&section.lines));
+                Vec::new()
+            } else if section.is("Idempotency") || section.is("Idempotent") {
+                operation.parse_idempotency(&section.lines)
+            } else if section.is("Limits") {
+                operation.parse_limits(&section.lines)?
+            } else if section.is("Compliance") || section.is("Audit") {
+                operation.parse_compliance(&section.lines)?
+            } else if section.is("SLA") {
+                operation.parse_sla(&section.lines)?
+            } else if section.is("CORS") {
+                operation.parse_cors(&section.lines)?
+            } else if section.is("Caching") || section.is("Cache") {
+                operation.parse_caching(&section.lines)?
+            } else if section.is("Pagination") {
+                operation.parse_pagination(&section)?;
+                Vec::new()
+            } else if section.is("Rate Limits") || section.is("Rate Limit") {
+                operation.parse_rate_limits(&section.lines)?
+            } else if section.is("Permissions") {
+                operation.parse_permissions(&section.lines)?
+            } else if section.is("Panics") && config::panics_as_500() {
+                // it is how the server responds when a handler panics
+                operation.responses.push(ResponseDoc {
+                    status: Status::Code(500),
+                    description: comment::join(&section.lines),
+                });
+                Vec::new()
+            } else if section.is("See Also") {
+                operation.parse_see_also(&section.lines)
+            } else if section.is("Changelog") {
+                operation.parse_changelog(&section.lines)?
+            } else if let Some(protocol) = section.argument("Protocol") {
+                // the protocol is either in the heading or the first line
+                let (protocol, rest) = if protocol.is_empty() {
+                    let first = section
+                        .lines
+                        .iter()
+                        .position(|line| !line.text.trim().is_empty());
+                    let text = first.map_or("", |index| section.lines[index].text.trim());
+                    (
+                        text.to_owned(),
+                        section.lines[first.map_or(0, |index| index + 1)..].to_vec(),
+                    )
+                } else {
+                    (protocol.to_owned(), section.lines.clone())
+                };
+                operation.protocol = Some(Protocol::parse(&protocol).ok_or_else(|| {
+                    syn::Error::new(
+                        section.heading.span,
+                        format!("unknown protocol `{protocol}`, expected `websocket` or `sse`"),
+                    )
+                })?);
+                rest
+            } else if let Some(version) = section.argument("Since") {
+                // the version is either in the heading or the only content
+                if version.is_empty() {
+                    operation.since = Some(comment::join(&section.lines));
+                    Vec::new()
+                } else {
+                    operation.since = Some(version.to_owned());
+                    section.lines.clone()
+                }
+            } else {
+                if !stripped.iter().any(|name| section.is(name)) {
+                    remaining.push(section.heading);
+                    remaining.extend(section.lines);
+                }
+                continue;
+            };
+
+            // keep anything that wasn't understood in the description
+            if !rest.iter().all(|line| line.text.trim().is_empty())
+                && !stripped.iter().any(|name| section.is(name))
+            {
+                remaining.push(section.heading);
+                remaining.extend(rest);
+            }
+        }
+
+        if let Some(since) = &operation.since {
+            operation
+                .extensions
+                .push(("x-since".to_owned(), since.clone().into()));
+
+            // operations that are newer than the version the documentation is
+            // generated for are left out by the transforms, and otherwise by
+            // tools that understand `x-internal`
+            if let Some(target) = config::target_version() {
+                if is_newer(since, &target) {
+                    operation.unreleased = true;
+                    operation
+                        .extensions
+                        .push(("x-internal".to_owned(), true.into()));
+                }
+            }
+        }
+
+        if let Some((bytes, size)) = operation.max_request_size.take() {
+            operation
+                .extensions
+                .push(("x-max-request-size".to_owned(), bytes.into()));
+
+            let description = operation.request_body.take().unwrap_or_default();
+            let limit = format!("The request body can be at most {size}.");
+            operation.request_body = Some(format!("{description}\n\n{limit}").trim().to_owned());
+        }
+
+        if let Some(pagination) = operation.pagination.take() {
+            let returns = operation.returns.take().unwrap_or_default();
+            operation.returns = Some(format!("{returns}\n\n{pagination}").trim().to_owned());
+        }
+
+        if !operation.rate_limits.is_empty() {
+            let limits = operation
+                .rate_limits
+                .iter()
+                .map(RateLimitDoc::describe)
+                .collect::<Vec<_>>();
+            remaining.push(Line {
+                text: String::new(),
+                span: Span::call_site(),
+            });
+            remaining.push(Line {
+                text: format!("Rate limits: {}.", limits.join(", ")),
+                span: Span::call_site(),
+            });
+
+            for limit in std::mem::take(&mut operation.rate_limits) {
+                let value = serde_json::json!({
+                    "limit": limit.limit,
+                    "period": limit.period,
+                    "scope": limit.scope,
+                });
+                operation.push_extension_item("x-rate-limit", value);
+            }
+        }
+
+        if let Some(protocol) = operation.protocol {
+            operation
+                .extensions
+                .push(("x-protocol".to_owned(), protocol.name().into()));
+
+            remaining.push(Line {
+                text: String::new(),
+                span: Span::call_site(),
+            });
+            remaining.push(Line {
+                text: protocol.semantics().to_owned(),
+                span: Span::call_site(),
+            });
+
+            if let Protocol::WebSocket = protocol {
+                if !operation
+                    .responses
+                    .iter()
+                    .any(|response| response.status == Status::Code(101))
+                {
+                    operation.responses.push(ResponseDoc {
+                        status: Status::Code(101),
+                        description: "Switching to the WebSocket protocol.".to_owned(),
+                    });
+                }
+            }
+        }
+
+        let (summary, badges) = take_badges(&doc.summary);
+        for badge in badges {
+            operation.push_extension_item("x-badges", serde_json::json!({ "name": badge }));
+        }
+        operation.summary = summary.to_owned();
+        if let (Some(protocol), false) = (operation.protocol, operation.summary.is_empty()) {
+            operation.summary = format!("{} ({})", operation.summary, protocol.label());
+        }
+        operation.description = comment::join(&remaining);
+
+        if let Some(front_matter) = doc.front_matter {
+            operation.apply_front_matter(&front_matter)?;
+        }
+
+        Ok(operation)
+    }
+
+    /// Sets an extension, or one of the fields that can be set in comments,
+    /// from a directive.
+    fn apply_directive(&mut self, directive: &Directive) -> syn::Result<()> {
+        let value = directive.value.as_str();
+
+        match directive.name.as_str() {
+            name if name.starts_with("x-") => {
+                // a directive without a value is a flag
+                let value = match value {
+                    "" => Value::Bool(true),
+                    value => yaml::parse(value).map_err(|err| {
+                        syn::Error::new(directive.span, format!("invalid `{name}`: {err}"))
+                    })?,
+                };
+                self.extensions.push((name.to_owned(), value));
+            }
+            "tag" | "tags" => self.tags.extend(
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|tag| !tag.is_empty())
+                    .map(str::to_owned),
+            ),
+            "operationId" | "operation_id" => self.operation_id = Some(value.to_owned()),
+            "deprecated" => self.deprecated = Some(value.to_owned()),
+            name => {
+                return Err(syn::Error::new(
+                    directive.span,
+                    format!("unknown directive `{name}`"),
+                ))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets the field of the operation that a JSDoc-style tag is for.
+    fn apply_tag(&mut self, tag: &Tag) -> syn::Result<()> {
+        // a `{Type}` is not needed since it is known from the handler
+        let text = match tag.text.strip_prefix('{') {
+            Some(rest) => rest.split_once('}').map_or("", |(_, rest)| rest.trim()),
+            None => &tag.text,
+        };
+        let (word, rest) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+        let rest = rest.trim().trim_start_matches(['-', ':']).trim();
+
+        let location = match tag.name.as_str() {
+            "query" => Some(Location::Query),
+            "path" => Some(Location::Path),
+            "header" => Some(Location::Header),
+            "cookie" => Some(Location::Cookie),
+            _ => None,
+        };
+
+        match tag.name.as_str() {
+            "param" | "arg" | "argument" | "query" | "path" | "header" | "cookie" => {
+                // `[name]` is how an optional parameter is written
+                let (name, optional) = match word.strip_prefix('[') {
+                    Some(name) => (name.trim_end_matches(']'), true),
+                    None => (word.trim_matches('`'), false),
+                };
+                let mut parameter = ParameterDoc::parse(name, rest, location, tag.span)?;
+                if optional {
+                    parameter.required = Some(false);
+                }
+                self.parameters.push(parameter);
+            }
+            "returns" | "return" => self.returns = Some(text.to_owned()),
+            "throws" | "response" => {
+                let status = Status::parse(word).ok_or_else(|| {
+                    syn::Error::new(tag.span, format!("invalid status code `{word}`"))
+                })?;
+                self.responses.push(ResponseDoc {
+                    status,
+                    description: rest.to_owned(),
+                });
+            }
+            "deprecated" => self.deprecated = Some(text.to_owned()),
+            "tags" | "tag" => self.tags.extend(
+                text.split([',', ' '])
+                    .map(str::trim)
+                    .filter(|tag| !tag.is_empty())
+                    .map(str::to_owned),
+            ),
+            "since" => self.since = Some(word.to_owned()),
+            "see" => self.external_docs = link(text),
+            "operationId" => self.operation_id = Some(word.to_owned()),
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Sets the fields given in YAML front matter, which take precedence over
+    /// the rest of the doc comment.
+    fn apply_front_matter(&mut self, front_matter: &FrontMatter) -> syn::Result<()> {
+        let error = |message: String| syn::Error::new(front_matter.span, message);
+
+        let fields = match yaml::parse(&front_matter.content) {
+            Ok(Value::Object(fields)) => fields,
+            Ok(Value::Null) => return Ok(()),
+            Ok(_) => return Err(error("front matter should be a mapping".to_owned())),
+            Err(err) => return Err(error(format!("invalid front matter: {err}"))),
+        };
+
+        for (key, value) in fields {
+            let invalid = || error(format!("invalid value for front matter field `{key}`"));
+            let string = |value: &Value| value.as_str().map(str::to_owned).ok_or_else(invalid);
+
+            match key.as_str() {
+                "tags" => match &value {
+                    Value::Array(tags) => {
+                        for tag in tags {
+                            self.tags.push(string(tag)?);
+                        }
+                    }
+                    tag => self.tags.push(string(tag)?),
+                },
+                "operationId" => self.operation_id = Some(string(&value)?),
+                "summary" => self.summary = string(&value)?,
+                "description" => self.description = string(&value)?,
+                "deprecated" => match value {
+                    Value::Bool(true) => {
+                        self.deprecated.get_or_insert_with(String::new);
+                    }
+                    Value::Bool(false) => self.deprecated = None,
+                    _ => return Err(invalid()),
+                },
+                "externalDocs" => {
+                    let url = value.get("url").ok_or_else(invalid)?;
+                    let description = value.get("description").map(string).transpose()?;
+                    self.external_docs = Some((string(url)?, description));
+                }
+                "servers" => {
+                    for server in value.as_array().ok_or_else(invalid)? {
+                        let url = server.get("url").unwrap_or(server);
+                        let description = server.get("description").map(string).transpose()?;
+                        self.servers.push((string(url)?, description));
+                    }
+                }
+                "security" => {
+                    for requirement in value.as_array().ok_or_else(invalid)? {
+                        let schemes = requirement
+                            .as_object()
+                            .ok_or_else(invalid)?
+                            .iter()
+                            .map(|(scheme, scopes)| {
+                                let scopes = match scopes {
+                                    Value::Array(scopes) => {
+                                        scopes.iter().map(string).collect::<Result<_, _>>()?
+                                    }
+                                    Value::Null => Vec::new(),
+                                    _ => return Err(invalid()),
+                                };
+                                Ok((scheme.clone(), scopes))
+                            })
+                            .collect::<syn::Result<_>>()?;
+                        self.security.push(schemes);
+                    }
+                }
+                _ if key.starts_with("x-") => self.extensions.push((key.clone(), value)),
+                _ => return Err(error(format!("unsupported front matter field `{key}`"))),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parses `- 404: description` list items into responses, returning the
+    /// lines that weren't list items.
+    fn parse_responses(&mut self, lines: &[Line]) -> syn::Result<Vec<Line>> {
+        comment::bullets(lines, |bullet| {
+            let Some((key, description)) = comment::key_value(&bullet.text) else {
+                return Ok(false);
+            };
+
+            let Some(status) = Status::parse(key) else {
+                if key.starts_with(|c: char| c.is_ascii_digit()) {
+                    return Err(syn::Error::new(
+                        bullet.span,
+                        format!("invalid status code `{key}`"),
+                    ));
+                }
+                return Ok(false);
+            };
+
+            self.responses.push(ResponseDoc {
+                status,
+                description: description.to_owned(),
+            });
+
+            Ok(true)
+        })
+    }
+
+    /// Parses ``- `name`: description`` list items into headers of the
+    /// successful response, returning the lines that weren't list items.
+    ///
+    /// Items under a sub-heading like `## 201` are set on that response
+    /// instead.
+    fn parse_response_headers(&mut self, lines: &[Line]) -> syn::Result<Vec<Line>> {
+        let mut groups = vec![(None, Vec::new())];
+        for line in lines {
+            let status = comment::heading_name(line.text.trim()).and_then(Status::parse);
+            match status {
+                Some(status) => groups.push((Some(status), Vec::new())),
+                None => groups.last_mut().unwrap().1.push(line.clone()),
+            }
+        }
+
+        let mut rest = Vec::new();
+        for (status, lines) in groups {
+            rest.extend(comment::bullets(&lines, |bullet| {
+                let Some((name, description)) = comment::key_value(&bullet.text) else {
+                    return Ok(false);
+                };
+
+                let (description, example) = example_value(description);
+                let (description, required) = required_flag(&description);
+
+                self.response_headers.push(ResponseHeaderDoc {
+                    status,
+                    name: name.to_owned(),
+                    description: description.to_owned(),
+                    required,
+                    example,
+                });
+
+                Ok(true)
+            })?);
+        }
+
+        Ok(rest)
+    }
+
+    /// Parses ``- `name`: description`` list items into parameters, returning
+    /// the lines that weren't list items.
+    ///
+    /// A description ending in `required` or `optional` sets whether the
+    /// parameter is required.
+    fn parse_parameters(
+        &mut self,
+        lines: &[Line],
+        location: Option<Location>,
+    ) -> syn::Result<Vec<Line>> {
+        comment::bullets(lines, |bullet| {
+            let Some((name, description)) = comment::key_value(&bullet.text) else {
+                return Ok(false);
+            };
+
+            let parameter = ParameterDoc::parse(name, description, location, bullet.span)?;
+            self.parameters.push(parameter);
+
+            Ok(true)
+        })
+    }
+
+    /// Parses `- name (type, required): description` list items into the
+    /// fields of a multipart request body, returning the lines that weren't
+    /// list items.
+    fn parse_upload(&mut self, lines: &[Line]) -> syn::Result<Vec<Line>> {
+        comment::bullets(lines, |bullet| {
+            let Some((field, description)) = comment::key_value(&bullet.text) else {
+                return Ok(false);
+            };
+
+            let (name, flags) = match field.split_once('(') {
+                Some((name, flags)) => (name.trim(), flags.trim_end_matches(')')),
+                None => (field, ""),
+            };
+            let name = name.trim_matches('`');
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return Ok(false);
+            }
+
+            let (description, mut required) = required_flag(description);
+            let mut schema_type = SchemaType::String;
+            for flag in flags
+                .split(',')
+                .map(str::trim)
+                .filter(|flag| !flag.is_empty())
+            {
+                match flag {
+                    "required" => required = Some(true),
+                    "optional" => required = Some(false),
+                    _ => {
+                        schema_type = SchemaType::parse(flag).ok_or_else(|| {
+                            syn::Error::new(
+                                bullet.span,
+                                format!("unknown field type `{flag}`, expected `string`, `integer`, `number`, `boolean`, or `binary`"),
+                            )
+                        })?;
+                    }
+                }
+            }
+
+            self.upload_fields.push(UploadFieldDoc {
+                name: name.to_owned(),
+                description: description.to_owned(),
+                schema_type,
+                required: required.unwrap_or(false),
+            });
+
+            Ok(true)
+        })
+    }
+
+    /// Parses `- scheme` list items into alternative security requirements,
+    /// returning the lines that weren't list items.
+    ///
+    /// Schemes that are required together are joined with `+`, and anything
+    /// after a `:` describes the requirement.
+    fn parse_security(&mut self, lines: &[Line]) -> syn::Result<Vec<Line>> {
+        comment::bullets(lines, |bullet| {
+            let schemes = match comment::key_value(&bullet.text) {
+                Some((schemes, _)) => schemes,
+                None => &bullet.text,
+            };
+
+            let schemes = schemes
+                .split('+')
+                .map(|scheme| {
+                    let scheme = scheme.trim();
+                    let scheme = scheme
+                        .strip_prefix('`')
+                        .and_then(|scheme| scheme.strip_suffix('`'))
+                        .unwrap_or(scheme);
+                    (scheme.to_owned(), Vec::new())
+                })
+                .collect::<Vec<_>>();
+
+            if schemes
+                .iter()
+                .any(|(scheme, _)| scheme.is_empty() || scheme.contains(char::is_whitespace))
+            {
+                return Ok(false);
+            }
+
+            self.security.push(schemes);
+
+            Ok(true)
+        })
+    }
+
+    /// Parses `- scope` list items, returning the lines that weren't list
+    /// items.
+    ///
+    /// Anything after the first word describes the scope.
+    fn parse_scopes(&mut self, lines: &[Line]) -> syn::Result<Vec<Line>> {
+        comment::bullets(lines, |bullet| {
+            let scope = comment::first_word(&bullet.text);
+            if scope.is_empty() {
+                return Ok(false);
+            }
+
+            self.scopes.push(scope.to_owned());
+
+            Ok(true)
+        })
+    }
+
+    /// Parses `- https://example.com: description` list items into servers,
+    /// returning the lines that weren't list items.
+    fn parse_servers(&mut self, lines: &[Line]) -> syn::Result<Vec<Line>> {
+        comment::bullets(lines, |bullet| {
+            let (url, description) = bullet
+                .text
+                .split_once(char::is_whitespace)
+                .unwrap_or((&bullet.text, ""));
+
+            // the `:` can't be split on directly since it is part of the URL
+            let url = url.strip_suffix(':').unwrap_or(url);
+            let url = url.trim_matches(|c| matches!(c, '`' | '<' | '>'));
+            if !url.contains("://") && !url.starts_with('/') {
+                return Ok(false);
+            }
+
+            let description = description.trim_start_matches([':', '-', '—']).trim();
+            let description = (!description.is_empty()).then(|| description.to_owned());
+
+            self.servers.push((url.to_owned(), description));
+
+            Ok(true)
+        })
+    }
+
+    /// Parses `- name: POST {$request.body#/url} description` list items into
+    /// callbacks, returning the lines that weren't list items.
+    fn parse_callbacks(&mut self, lines: &[Line]) -> syn::Result<Vec<Line>> {
+        comment::bullets(lines, |bullet| {
+            let Some((name, rest)) = comment::key_value(&bullet.text) else {
+                return Ok(false);
+            };
+
+            let mut parts = rest.splitn(3, char::is_whitespace);
+            let (Some(method), Some(url)) = (parts.next(), parts.next()) else {
+                return Ok(false);
+            };
+
+            let method = method.to_lowercase();
+            if !METHODS.contains(&method.as_str()) {
+                return Ok(false);
+            }
+
+            let url = url.strip_suffix(':').unwrap_or(url);
+            let url = url.trim_matches('`');
+            let description = parts.next().unwrap_or_default();
+            let description = description.trim_start_matches([':', '-', '—']).trim();
+
+            self.callbacks.push(CallbackDoc {
+                name: name.to_owned(),
+                method,
+                url: url.to_owned(),
+                description: description.to_owned(),
+            });
+
+            Ok(true)
+        })
+    }
+
+    /// Parses `- role` list items into the `x-permissions` extension,
+    /// returning the lines that weren't list items.
+    ///
+    /// Anything after the first word describes the permission.
+    fn parse_permissions(&mut self, lines: &[Line]) -> syn::Result<Vec<Line>> {
+        comment::bullets(lines, |bullet| {
+            let permission = comment::first_word(&bullet.text);
+            if permission.is_empty() {
+                return Ok(false);
+            }
+
+            self.push_extension_item("x-permissions", permission.into());
+
+            Ok(true)
+        })
+    }
+
+    /// Sets the `x-idempotent` extension from a section that starts with
+    /// "yes" or "no", or otherwise says whether the operation is idempotent,
+    /// returning the section as a normalized paragraph.
+    fn parse_idempotency(&mut self, lines: &[Line]) -> Vec<Line> {
+        let text = lines
+            .iter()
+            .map(|line| line.text.trim())
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let (first, rest) = text.split_once(char::is_whitespace).unwrap_or((&text, ""));
+
+        let (idempotent, prose) = match first
+            .trim_end_matches([',', '.', ':'])
+            .to_lowercase()
+            .as_str()
+        {
+            "yes" | "true" => (true, rest.trim()),
+            "no" | "false" => (false, rest.trim()),
+            _ => {
+                let lowercase = text.to_lowercase();
+                let negated = ["not idempotent", "non-idempotent", "isn't idempotent"]
+                    .iter()
+                    .any(|phrase| lowercase.contains(phrase));
+                (!negated, text.as_str())
+            }
+        };
+
+        self.extensions
+            .push(("x-idempotent".to_owned(), idempotent.into()));
+
+        let summary = if idempotent {
+            "This operation is idempotent."
+        } else {
+            "This operation is not idempotent."
+        };
+        let span = lines.first().map_or_else(Span::call_site, |line| line.span);
+
+        // what followed a "yes" or "no" is a sentence of its own
+        let mut chars = prose.chars();
+        let prose = match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        };
+
+        vec![
+            Line {
+                text: String::new(),
+                span,
+            },
+            Line {
+                text: format!("{summary} {prose}").trim().to_owned(),
+                span,
+            },
+        ]
+    }
+
+    /// Parses a `- request body: 10 MB` list item into the largest request
+    /// size, returning the lines that weren't taken.
+    fn parse_limits(&mut self, lines: &[Line]) -> syn::Result<Vec<Line>> {
+        comment::bullets(lines, |bullet| {
+            let Some((key, value)) = comment::key_value(&bullet.text) else {
+                return Ok(false);
+            };
+
+            let key = key.to_lowercase();
+            if !["request", "body", "payload", "upload"]
+                .iter()
+                .any(|word| key.contains(word))
+            {
+                return Ok(false);
+            }
+
+            let size = value.trim_end_matches('.').replace('`', "");
+            let Some(bytes) = size_bytes(&size) else {
+                return Err(syn::Error::new(
+                    bullet.span,
+                    format!("invalid size `{value}`, expected something like `10 MB`"),
+                ));
+            };
+            self.max_request_size = Some((bytes, size));
+
+            Ok(true)
+        })
+    }
+
+    /// Parses `- classification: confidential` and `- pii: email, name` list
+    /// items into the `x-data-classification` and `x-pii` extensions,
+    /// returning the lines that weren't taken.
+    fn parse_compliance(&mut self, lines: &[Line]) -> syn::Result<Vec<Line>> {
+        comment::bullets(lines, |bullet| {
+            let Some((key, value)) = comment::key_value(&bullet.text) else {
+                return Ok(false);
+            };
+            let value = value.trim_end_matches('.').replace('`', "");
+
+            match key.to_lowercase().as_str() {
+                "classification" | "data classification" => {
+                    self.extensions.push((
+                        "x-data-classification".to_owned(),
+                        value.to_lowercase().into(),
+                    ));
+                }
+                "pii" | "personal data" => {
+                    // the kinds of personal data that are handled
+                    let kinds = match value.to_lowercase().as_str() {
+                        "none" | "no" => Vec::new(),
+                        _ => value
+                            .split(',')
+                            .map(|kind| Value::from(kind.trim()))
+                            .filter(|kind| kind != "")
+                            .collect(),
+                    };
+                    self.extensions
+                        .push(("x-pii".to_owned(), Value::Array(kinds)));
+                }
+                _ => return Ok(false),
+            }
+
+            Ok(true)
+        })
+    }
+
+    /// Parses `- p99 < 200ms` and `- availability: 99.9%` list items into
+    /// the `x-sla` extension, returning the lines that weren't list items.
+    fn parse_sla(&mut self, lines: &[Line]) -> syn::Result<Vec<Line>> {
+        let mut latency = serde_json::Map::new();
+        let mut availability = None;
+
+        let rest = comment::bullets(lines, |bullet| {
+            let text = bullet.text.replace('`', "");
+            let (key, value) = text
+                .split_once(['<', ':', '='])
+                .map(|(key, value)| (key.trim(), value.trim_start_matches(['<', '=']).trim()))
+                .unwrap_or((&text, ""));
+            let key = key.to_lowercase();
+
+            if key.starts_with('p') && key[1..].chars().all(|c| c.is_ascii_digit() || c == '.') {
+                let Some(milliseconds) = duration_ms(value) else {
+                    return Err(syn::Error::new(
+                        bullet.span,
+                        format!("invalid latency `{value}`, expected something like `200ms`"),
+                    ));
+                };
+                latency.insert(key, milliseconds.into());
+            } else if key == "availability" || key == "uptime" {
+                let percent = value.trim_end_matches('%').trim().parse::<f64>();
+                let Ok(percent) = percent else {
+                    return Err(syn::Error::new(
+                        bullet.span,
+                        format!("invalid availability `{value}`, expected something like `99.9%`"),
+                    ));
+                };
+                availability = Some(percent);
+            } else {
+                return Ok(false);
+            }
+
+            Ok(true)
+        })?;
+
+        let mut sla = serde_json::Map::new();
+        if !latency.is_empty() {
+            sla.insert("latencyMs".to_owned(), Value::Object(latency));
+        }
+        if let Some(availability) = availability {
+            sla.insert("availability".to_owned(), availability.into());
+        }
+        if !sla.is_empty() {
+            self.extensions
+                .push(("x-sla".to_owned(), Value::Object(sla)));
+        }
+
+        Ok(rest)
+    }
+
+    /// Parses `- origins: https://example.com` list items into the `x-cors`
+    /// extension, returning the lines that weren't list items.
+    ///
+    /// The keys are `origins`, `methods`, and `headers` with comma-separated
+    /// values, `credentials` with a yes or no, and `max age` with a number of
+    /// seconds.
+    fn parse_cors(&mut self, lines: &[Line]) -> syn::Result<Vec<Line>> {
+        let mut cors = serde_json::Map::new();
+
+        let rest = comment::bullets(lines, |bullet| {
+            let Some((key, value)) = comment::key_value(&bullet.text) else {
+                return Ok(false);
+            };
+
+            let key = key.to_lowercase().replace(['-', '_', ' '], "");
+            let key = key
+                .trim_start_matches("accesscontrol")
+                .trim_start_matches("allowed")
+                .trim_start_matches("allow");
+            let list = || {
+                let items = value
+                    .split(',')
+                    .map(|item| item.trim().trim_matches('`'))
+                    .filter(|item| !item.is_empty())
+                    .map(|item| Value::String(item.to_owned()));
+                Value::Array(items.collect())
+            };
+
+            let (key, value) = match key {
+                "origin" | "origins" => ("allowedOrigins", list()),
+                "method" | "methods" => ("allowedMethods", list()),
+                "header" | "headers" => ("allowedHeaders", list()),
+                "exposeheaders" | "exposedheaders" => ("exposedHeaders", list()),
+                "credentials" => {
+                    let value = value.trim_end_matches('.').to_lowercase();
+                    (
+                        "allowCredentials",
+                        matches!(value.as_str(), "yes" | "true").into(),
+                    )
+                }
+                "maxage" => {
+                    let seconds = value.trim_end_matches(['s', '.']).trim().parse::<u64>();
+                    let Ok(seconds) = seconds else {
+                        return Err(syn::Error::new(
+                            bullet.span,
+                            format!("invalid CORS max age `{value}`"),
+                        ));
+                    };
+                    ("maxAge", seconds.into())
+                }
+                _ => return Ok(false),
+            };
+            cors.insert(key.to_owned(), value);
+
+            Ok(true)
+        })?;
+
+        if !cors.is_empty() {
+            self.extensions
+                .push(("x-cors".to_owned(), Value::Object(cors)));
+        }
+
+        Ok(rest)
+    }
+
+    /// Parses ``- `Cache-Control`: public, max-age=60`` and
+    /// ``- `ETag`: description`` list items into headers of the successful
+    /// response, returning the lines that weren't list items.
+    ///
+    /// The `x-cacheable` extension is set to whether the section allows the
+    /// response to be stored.
+    fn parse_caching(&mut self, lines: &[Line]) -> syn::Result<Vec<Line>> {
+        let text = comment::join(lines).to_lowercase();
+        let cacheable = !["no-store", "not cacheable", "not cached", "never cached"]
+            .iter()
+            .any(|phrase| text.contains(phrase));
+        self.extensions
+            .push(("x-cacheable".to_owned(), cacheable.into()));
+
+        comment::bullets(lines, |bullet| {
+            let Some((name, description)) = comment::key_value(&bullet.text) else {
+                return Ok(false);
+            };
+
+            let is_header = [
+                "Cache-Control",
+                "ETag",
+                "Expires",
+                "Last-Modified",
+                "Vary",
+                "Age",
+            ]
+            .iter()
+            .any(|header| header.eq_ignore_ascii_case(name));
+            if !is_header {
+                return Ok(false);
+            }
+
+            // a value like `public, max-age=60` is an example of the header
+            let is_directives = description.split(',').all(|directive| {
+                let directive = directive.trim().trim_matches('`');
+                let (name, value) = directive.split_once('=').unwrap_or((directive, "0"));
+                !name.is_empty()
+                    && name.chars().all(|c| c.is_ascii_alphabetic() || c == '-')
+                    && value.chars().all(|c| c.is_ascii_digit())
+            });
+            let example = (name.eq_ignore_ascii_case("Cache-Control") && is_directives)
+                .then(|| Value::String(description.replace('`', "")));
+
+            self.response_headers.push(ResponseHeaderDoc {
+                status: None,
+                name: name.to_owned(),
+                description: description.to_owned(),
+                required: None,
+                example,
+            });
+
+            Ok(true)
+        })
+    }
+
+    /// Adds the parameters of the pagination profile, with the contents of the
+    /// section describing the results along with the profile.
+    fn parse_pagination(&mut self, section: &Section) -> syn::Result<()> {
+        let profile = config::pagination_profile();
+        let (parameters, envelope) = match profile.as_str() {
+            "page" => (
+                [
+                    ("page", "The page of results to return, starting at 1."),
+                    ("per_page", "The number of results on each page."),
+                ],
+                "The results are paginated, with `items` holding the results on \
+                 this page and `total` the number of results on all pages.",
+            ),
+            "offset" => (
+                [
+                    ("offset", "The number of results to skip."),
+                    ("limit", "The maximum number of results to return."),
+                ],
+                "The results are paginated, with `items` holding the results \
+                 that were returned and `total` the number of all results.",
+            ),
+            "cursor" => (
+                [
+                    (
+                        "cursor",
+                        "The cursor of the previous page, to get the next one.",
+                    ),
+                    ("limit", "The maximum number of results to return."),
+                ],
+                "The results are paginated, with `items` holding the results on \
+                 this page and `next_cursor` the cursor of the next page, which \
+                 is missing on the last page.",
+            ),
+            _ => {
+                return Err(syn::Error::new(
+                    section.heading.span,
+                    format!("unknown pagination profile `{profile}`"),
+                ))
+            }
+        };
+
+        for (name, description) in parameters {
+            let mut parameter = ParameterDoc::parse(
+                name,
+                description,
+                Some(Location::Query),
+                section.heading.span,
+            )?;
+            parameter.required = Some(false);
+            parameter.add_if_missing = true;
+            if name != "cursor" {
+                parameter.schema_type = SchemaType::Integer;
+                parameter.minimum = Some(if name == "offset" { 0.0 } else { 1.0 });
+            }
+            self.parameters.push(parameter);
+        }
+
+        let description = comment::join(&section.lines);
+        self.pagination = Some(format!("{envelope}\n\n{description}").trim().to_owned());
+
+        Ok(())
+    }
+
+    /// Parses `- 100 requests/minute per token` list items into rate limits,
+    /// returning the lines that weren't list items.
+    fn parse_rate_limits(&mut self, lines: &[Line]) -> syn::Result<Vec<Line>> {
+        comment::bullets(lines, |bullet| {
+            let Some(limit) = RateLimitDoc::parse(&bullet.text) else {
+                return Ok(false);
+            };
+
+            self.rate_limits.push(limit);
+
+            Ok(true)
+        })
+    }
+
+    /// Takes the first line with a link as the external documentation,
+    /// returning the other lines.
+    ///
+    /// The text of the line, without the URL, describes the documentation.
+    fn parse_see_also(&mut self, lines: &[Line]) -> Vec<Line> {
+        let mut rest = Vec::new();
+
+        for line in lines {
+            if self.external_docs.is_none() {
+                if let Some(link) = link(&line.text) {
+                    self.external_docs = Some(link);
+                    continue;
+                }
+            }
+            rest.push(line.clone());
+        }
+
+        rest
+    }
+
+    /// Parses `- 1.4: description` list items into the `x-changelog`
+    /// extension, returning the lines that weren't list items.
+    fn parse_changelog(&mut self, lines: &[Line]) -> syn::Result<Vec<Line>> {
+        comment::bullets(lines, |bullet| {
+            let is_version = |version: &str| {
+                version
+                    .trim_start_matches(['v', 'V'])
+                    .starts_with(|c: char| c.is_ascii_digit())
+            };
+
+            let entry = match comment::key_value(&bullet.text) {
+                Some((version, description)) if is_version(version) => serde_json::json!({
+                    "version": version,
+                    "description": description,
+                }),
+                _ => serde_json::json!({ "description": bullet.text }),
+            };
+            self.push_extension_item("x-changelog", entry);
+
+            Ok(true)
+        })
+    }
+
+    /// Takes code blocks in a known language as examples of the request body
+    /// and the rest as its description.
+    fn parse_request_body(&mut self, lines: &[Line]) -> syn::Result<Vec<Line>> {
+        let mut has_examples = false;
+        let rest = comment::fences(lines, |fence| {
+            let Some(media_type) = media_type(&fence.lang) else {
+                return Ok(false);
+            };
+
+            self.examples.push(ExampleDoc {
+                target: ExampleTarget::RequestBody,
+                name: fence.heading.clone(),
+                media_type,
+                value: fence_value(fence, media_type)?.to_string(),
+            });
+            has_examples = true;
+
+            Ok(true)
+        })?;
+
+        let description = comment::join(&rest);
+        if !description.is_empty() || !has_examples {
+            self.request_body = Some(description);
+        }
+
+        Ok(Vec::new())
+    }
+
+    /// Parses code blocks in a known language like ```` ```json ```` into
+    /// examples of that media type, returning the lines that weren't taken.
+    ///
+    /// Code blocks under a sub-heading are named by it. The sub-heading or the
+    /// line before a code block decides where it is attached to if it mentions
+    /// a "request" or "response".
+    fn parse_examples(&mut self, lines: &[Line]) -> syn::Result<Vec<Line>> {
+        let mut labels = Vec::new();
+        let mut headings = Vec::new();
+
+        let rest = comment::fences(lines, |fence| {
+            if let Some((lang, label)) = code_sample_lang(&fence.lang) {
+                let sample = serde_json::json!({
+                    "lang": lang,
+                    "label": fence.heading.as_deref().unwrap_or(label),
+                    "source": fence.content,
+                });
+                self.push_extension_item("x-codeSamples", sample);
+                headings.extend(fence.heading.clone());
+                return Ok(true);
+            }
+
+            let Some(media_type) = media_type(&fence.lang) else {
+                return Ok(false);
+            };
+
+            let value = fence_value(fence, media_type)?;
+
+            let label = [&fence.label, &fence.heading]
+                .into_iter()
+                .flatten()
+                .map(|text| text.to_lowercase())
+                .collect::<String>();
+            // a heading like `### 404 Not Found` is the response with that
+            // status, and names the example if there is more to it
+            let status = |text: &str| {
+                let (status, rest) = text.split_once(' ').unwrap_or((text, ""));
+                let status = Status::parse(status.trim_end_matches(':'))?;
+                let rest = rest.trim().trim_end_matches(':').trim();
+                Some((status, (!rest.is_empty()).then(|| rest.to_owned())))
+            };
+            let heading_status = fence.heading.as_deref().and_then(status);
+            let label_status = fence.label.as_deref().and_then(status);
+
+            let (target, name) = if let Some((status, name)) = heading_status {
+                (ExampleTarget::Status(status), name)
+            } else if let Some((status, _)) = label_status {
+                (ExampleTarget::Status(status), fence.heading.clone())
+            } else if label.contains("request") {
+                (ExampleTarget::RequestBody, fence.heading.clone())
+            } else if label.contains("response") {
+                (ExampleTarget::Response, fence.heading.clone())
+            } else {
+                (ExampleTarget::Auto, fence.heading.clone())
+            };
+
+            labels.extend(fence.label.clone());
+            headings.extend(fence.heading.clone());
+            self.examples.push(ExampleDoc {
+                target,
+                name,
+                media_type,
+                value: value.to_string(),
+            });
+
+            Ok(true)
+        })?;
+
+        // labels like "Request:" and the sub-headings only introduce the code
+        // blocks that were taken
+        let rest = rest
+            .into_iter()
+            .filter(|line| {
+                let text = line.text.trim();
+                let is_label = text.ends_with(':') && labels.iter().any(|label| label == text);
+                let is_heading = comment::heading_name(text)
+                    .is_some_and(|name| headings.iter().any(|heading| heading == name));
+                !is_label && !is_heading
+            })
+            .collect();
+
+        Ok(rest)
+    }
+
+    /// Adds a value to an extension holding an array, creating it if needed.
+    pub(crate) fn push_extension_item(&mut self, key: &str, value: serde_json::Value) {
+        let existing = self
+            .extensions
+            .iter_mut()
+            .find(|(existing, _)| existing == key);
+
+        match existing {
+            Some((_, serde_json::Value::Array(items))) => items.push(value),
+            Some((_, existing)) => *existing = serde_json::Value::Array(vec![value]),
+            None => self
+                .extensions
+                .push((key.to_owned(), serde_json::Value::Array(vec![value]))),
+        }
+    }
+
+    /// Whether something is documented on the successful response.
+    pub(crate) fn documents_success(&self) -> bool {
+        self.returns.is_some()
+            || !self.produces.is_empty()
+            || self
+                .response_headers
+                .iter()
+                .any(|header| header.status.is_none())
+            || self.examples.iter().any(|example| {
+                matches!(
+                    example.target,
+                    ExampleTarget::Auto | ExampleTarget::Response
+                )
+            })
+    }
+
+    /// The statuses of the responses that something is documented on.
+    pub(crate) fn documented_statuses(&self) -> Vec<Status> {
+        let responses = self.responses.iter().map(|response| response.status);
+        let headers = self
+            .response_headers
+            .iter()
+            .filter_map(|header| header.status);
+        let examples = self
+            .examples
+            .iter()
+            .filter_map(|example| match example.target {
+                ExampleTarget::Status(status) => Some(status),
+                _ => None,
+            });
+
+        responses.chain(headers).chain(examples).collect()
+    }
+}
+
+/// Finds a `[text](url)`, `<url>`, or bare URL in a line, returning it along
+/// with the rest of the line.
+fn link(line: &str) -> Option<(String, Option<String>)> {
+    let text = line.trim();
+    let text = text
+        .strip_prefix("- ")
+        .or_else(|| text.strip_prefix("* "))
+        .unwrap_or(text);
+
+    let (url, description) = if let Some((before, after)) = text.split_once("](") {
+        let (label_start, label) = before.rsplit_once('[')?;
+        let (url, after) = after.split_once(')')?;
+        (url, format!("{label_start}{label}{after}"))
+    } else if let Some((before, after)) = text.split_once('<') {
+        let (url, after) = after.split_once('>')?;
+        (url, format!("{before}{after}"))
+    } else {
+        let url = text
+            .split_whitespace()
+            .find(|word| word.starts_with("http://") || word.starts_with("https://"))?;
+        let url = url.trim_end_matches(['.', ',', ')']);
+        (url, text.replacen(url, "", 1))
+    };
+
+    if !url.contains("://") {
+        return None;
+    }
+
+    let description = description.trim().trim_matches([':', '-', '—']).trim();
+    let description = (!description.is_empty()).then(|| description.to_owned());
+
+    Some((url.to_owned(), description))
+}
+
+/// Takes `[BETA]` and `[EXPERIMENTAL]` markers off the start of a summary,
+/// returning the rest of it along with the names of the badges.
+fn take_badges(summary: &str) -> (&str, Vec<&'static str>) {
+    let mut rest = summary.trim_start();
+    let mut badges = Vec::new();
+
+    while let Some((marker, after)) = rest.strip_prefix('[').and_then(|rest| rest.split_once(']')) {
+        let badge = match marker.trim().to_uppercase().as_str() {
+            "BETA" => "Beta",
+            "EXPERIMENTAL" => "Experimental",
+            _ => break,
+        };
+        badges.push(badge);
+        rest = after.trim_start();
+    }
+
+    (rest, badges)
+}
+
+/// Parses a size like `10 MB` or `512KiB` into bytes, where `KB` is 1000
+/// bytes and `KiB` is 1024 bytes.
+fn size_bytes(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let number_end = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let number = text[..number_end].parse::<f64>().ok()?;
+
+    let multiplier: u64 = match text[number_end..].trim().to_lowercase().as_str() {
+        "" | "b" | "bytes" => 1,
+        "kb" => 1000,
+        "mb" => 1000 * 1000,
+        "gb" => 1000 * 1000 * 1000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        _ => return None,
+    };
+
+    Some((number * multiplier as f64) as u64)
+}
+
+/// Parses a duration like `200ms`, `1.5s`, or `200 ms` into milliseconds.
+fn duration_ms(text: &str) -> Option<f64> {
+    let text = text.trim().trim_end_matches('.');
+    let number_end = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let number = text[..number_end].parse::<f64>().ok()?;
+
+    match text[number_end..].trim() {
+        "" | "ms" => Some(number),
+        "s" | "sec" | "seconds" => Some(number * 1000.0),
+        _ => None,
+    }
+}
+
+/// Whether the version is newer than the target, comparing the numbers that
+/// are separated by `.` in turn.
+fn is_newer(version: &str, target: &str) -> bool {
+    let numbers = |version: &str| {
+        version
+            .trim()
+            .trim_start_matches(['v', 'V'])
+            .split('.')
+            .map(|number| number.trim().parse::<u64>().unwrap_or(0))
+            .collect::<Vec<_>>()
+    };
+
+    let mut version = numbers(version);
+    let mut target = numbers(target);
+    let len = version.len().max(target.len());
+    version.resize(len, 0);
+    target.resize(len, 0);
+
+    version > target
+}
+
+/// Splits a trailing `, required` or `(optional)` from a parameter
+/// description.
+fn required_flag(description: &str) -> (&str, Option<bool>) {
+    for (flag, required) in [("required", true), ("optional", false)] {
+        if description == flag {
+            return ("", Some(required));
+        }
+
+        for suffix in [format!(", {flag}"), format!("({flag})")] {
+            if let Some(rest) = description.strip_suffix(&suffix) {
+                return (rest.trim_end(), Some(required));
+            }
+        }
+    }
+
+    (description, None)
+}
+
+/// Takes an `(e.g. 42)` out of a parameter description.
+fn example_value(description: &str) -> (String, Option<serde_json::Value>) {
+    let Some(start) = description.find("(e.g.") else {
+        return (description.to_owned(), None);
+    };
+    let Some(end) = description[start..].find(')').map(|end| start + end) else {
+        return (description.to_owned(), None);
+    };
+
+    let value = description[start + "(e.g.".len()..end].trim();
+    let value = value
+        .strip_prefix('`')
+        .and_then(|value| value.strip_suffix('`'))
+        .unwrap_or(value);
+    let value = json_or_string(value);
+
+    let before = description[..start].trim_end();
+    let after = description[end + 1..].trim_start();
+    let description = match (before.is_empty(), after) {
+        (_, "") => before.to_owned(),
+        (true, _) => after.to_owned(),
+        (false, after) if after.starts_with([',', '.']) => format!("{before}{after}"),
+        (false, after) => format!("{before} {after}"),
+    };
+
+    (description, Some(value))
+}
+
+/// Parses a value from the documentation as a number or boolean if it is one,
+/// and as a string otherwise.
+fn json_or_string(value: &str) -> serde_json::Value {
+    match serde_json::from_str(value) {
+        Ok(value @ (serde_json::Value::Number(_) | serde_json::Value::Bool(_))) => value,
+        _ => serde_json::Value::String(value.to_owned()),
+    }
+}
+
+/// The values in backticks following "one of" in a parameter description.
+///
+/// Values that are valid JSON, like numbers, are kept as such and everything
+/// else is a string.
+fn allowed_values(description: &str) -> Vec<serde_json::Value> {
+    // the offsets of ASCII lowercase are the same as in the description
+    let lowercase = description.to_ascii_lowercase();
+    let Some(start) = lowercase.find("one of ") else {
+        return Vec::new();
+    };
+
+    let mut values = Vec::new();
+    let mut rest = description[start + "one of ".len()..].trim_start();
+    while let Some(value) = rest.strip_prefix('`') {
+        let Some((value, after)) = value.split_once('`') else {
+            break;
+        };
+
+        values.push(json_or_string(value));
+
+        // the values are separated by commas, "or", or both
+        rest = after.trim_start();
+        rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+        rest = rest.strip_prefix("or ").unwrap_or(rest).trim_start();
+    }
+
+    values
+}
+
+/// The Redoc language and default label for a code block that is a sample of
+/// calling the operation.
+fn code_sample_lang(lang: &str) -> Option<(&'static str, &'static str)> {
+    match lang {
+        "curl" => Some(("Shell", "curl")),
+        "sh" | "bash" | "shell" => Some(("Shell", "Shell")),
+        "python" | "py" => Some(("Python", "Python")),
+        "javascript" | "js" => Some(("JavaScript", "JavaScript")),
+        "typescript" | "ts" => Some(("TypeScript", "TypeScript")),
+        "go" => Some(("Go", "Go")),
+        "java" => Some(("Java", "Java")),
+        "kotlin" => Some(("Kotlin", "Kotlin")),
+        "ruby" => Some(("Ruby", "Ruby")),
+        "php" => Some(("PHP", "PHP")),
+        "csharp" | "cs" => Some(("C#", "C#")),
+        "swift" => Some(("Swift", "Swift")),
+        _ => None,
+    }
+}
+
+/// The example in a code block of the given media type.
+fn fence_value(fence: &comment::Fence, media_type: &str) -> syn::Result<serde_json::Value> {
+    // other formats can only be given as a string
+    if media_type == "application/json" {
+        serde_json::from_str(&fence.content)
+            .map_err(|err| syn::Error::new(fence.span, format!("invalid JSON example: {err}")))
+    } else {
+        Ok(serde_json::Value::String(fence.content.clone()))
+    }
+}
+
+/// The media type for the language of a code block.
+fn media_type(lang: &str) -> Option<&'static str> {
+    match lang {
+        "json" => Some("application/json"),
+        "xml" => Some("application/xml"),
+        "yaml" | "yml" => Some("application/yaml"),
+        "html" => Some("text/html"),
+        "csv" => Some("text/csv"),
+        "text" | "txt" | "plain" => Some("text/plain"),
+        _ => None,
+    }
+}
@@ -0,0 +1,199 @@
+//! The `AideCommentParams` derive, which documents the parameters of an
+//! extractor type for the `params` argument of the attribute, and the
+//! `#[aidecomment(doc = "...")]` attribute on the arguments of a handler,
+//! which documents the parameters of single-value extractors.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, Fields, FnArg, LitStr, Meta, Pat, Signature, Token, Type};
+
+use crate::args;
+use crate::comment;
+use crate::operation::{Location, ParameterDoc, SchemaType};
+
+/// Generates the `AIDECOMMENT_PARAMETERS` constant of the type, which lists
+/// the names of its documented fields along with their descriptions.
+pub(crate) fn derive(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "`AideCommentParams` needs a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`AideCommentParams` can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut parameters = Vec::new();
+    for field in fields {
+        let description = comment::doc_lines(&field.attrs)?
+            .iter()
+            .map(|line| line.text.trim())
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_owned();
+        if description.is_empty() {
+            continue;
+        }
+
+        let name = match serde_rename(&field.attrs)? {
+            Some(name) => name,
+            None => field.ident.as_ref().unwrap().unraw().to_string(),
+        };
+        parameters.push(quote! { (#name, #description) });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #[doc(hidden)]
+            pub const AIDECOMMENT_PARAMETERS: &'static [(&'static str, &'static str)] = &[#(#parameters),*];
+        }
+    })
+}
+
+/// The name from a `#[serde(rename = "...")]` attribute, which is the name of
+/// the parameter instead of the field.
+fn serde_rename(attrs: &[Attribute]) -> syn::Result<Option<String>> {
+    let mut rename = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") && meta.input.peek(Token![=]) {
+                rename = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.input.peek(Token![=]) {
+                // the other options are serde's to check
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if !meta.input.is_empty() && !meta.input.peek(Token![,]) {
+                meta.parse_nested_meta(|_| Ok(()))?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(rename)
+}
+
+/// Removes the `#[aidecomment(doc = "...")]` attributes from the arguments of
+/// a handler, returning the parameters that they document.
+///
+/// The parameter is named after the binding, like `tenant` in
+/// `Path(tenant): Path<String>`, unless a `name` is given. aide doesn't add
+/// parameters for a `Path` of a single value, so those are added if they are
+/// missing.
+pub(crate) fn argument_docs(sig: &mut Signature) -> syn::Result<Vec<ParameterDoc>> {
+    let mut parameters = Vec::new();
+    for arg in &mut sig.inputs {
+        let FnArg::Typed(arg) = arg else {
+            continue;
+        };
+        let Some(index) = arg
+            .attrs
+            .iter()
+            .position(|attr| attr.path().is_ident("aidecomment"))
+        else {
+            continue;
+        };
+        let attr = arg.attrs.remove(index);
+
+        let mut name = None;
+        let mut description = None;
+        for meta in attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)? {
+            let path = meta.path();
+            if path.is_ident("doc") {
+                description = Some(args::string(&meta)?);
+            } else if path.is_ident("name") {
+                name = Some(args::string(&meta)?);
+            } else {
+                return Err(args::unknown(path, "`doc` or `name`"));
+            }
+        }
+        let Some(description) = description else {
+            return Err(syn::Error::new_spanned(
+                &attr,
+                "expected a `doc = \"...\"` argument",
+            ));
+        };
+        let Some(name) = name.or_else(|| binding(&arg.pat)) else {
+            return Err(syn::Error::new_spanned(
+                &arg.pat,
+                "the name of the parameter can't be told from the pattern, so it needs a `name = \"...\"` argument",
+            ));
+        };
+
+        let extractor = extractor(&arg.ty);
+        let location = extractor
+            .as_ref()
+            .and_then(|(extractor, _)| match extractor.as_str() {
+                "Path" => Some(Location::Path),
+                "Query" => Some(Location::Query),
+                "TypedHeader" | "Header" => Some(Location::Header),
+                _ => None,
+            });
+        let mut parameter = ParameterDoc::parse(&name, &description, location, attr.span())?;
+        if let (Some(Location::Path), Some((_, Some(inner)))) = (location, &extractor) {
+            parameter.add_if_missing = true;
+            parameter.required.get_or_insert(true);
+            parameter.schema_type = schema_type(inner);
+        }
+        parameters.push(parameter);
+    }
+
+    Ok(parameters)
+}
+
+/// The name bound by a pattern like `tenant` or `Path(tenant)`.
+fn binding(pat: &Pat) -> Option<String> {
+    match pat {
+        Pat::Ident(pat) => Some(pat.ident.unraw().to_string()),
+        Pat::TupleStruct(pat) if pat.elems.len() == 1 => binding(&pat.elems[0]),
+        Pat::Paren(pat) => binding(&pat.pat),
+        _ => None,
+    }
+}
+
+/// The name of an extractor like `Path<String>`, along with the type it
+/// extracts.
+fn extractor(ty: &Type) -> Option<(String, Option<Type>)> {
+    let Type::Path(ty) = ty else {
+        return None;
+    };
+    let segment = ty.path.segments.last()?;
+    let inner = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            syn::GenericArgument::Type(ty) => Some(ty.clone()),
+            _ => None,
+        }),
+        _ => None,
+    };
+    Some((segment.ident.to_string(), inner))
+}
+
+/// The type of a parameter that is added for a Rust type.
+fn schema_type(ty: &Type) -> SchemaType {
+    let Type::Path(ty) = ty else {
+        return SchemaType::String;
+    };
+    let Some(ident) = ty.path.get_ident() else {
+        return SchemaType::String;
+    };
+    match ident.to_string().as_str() {
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64" | "i128"
+        | "isize" => SchemaType::Integer,
+        "f32" | "f64" => SchemaType::Number,
+        "bool" => SchemaType::Boolean,
+        _ => SchemaType::String,
+    }
+}
@@ -0,0 +1,113 @@
+//! The `AideCommentResponse` derive, which describes the response of a
+//! wrapper type with its doc comment.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Expr, ExprLit, Lit};
+
+use crate::args::Args;
+use crate::comment;
+
+/// Generates an `OperationOutput` implementation for a type with one field,
+/// which responds like its field with the doc comment of the type as the
+/// description.
+pub(crate) fn derive(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`AideCommentResponse` can only be derived for structs",
+        ));
+    };
+    let mut fields = data.fields.iter();
+    let (Some(field), None) = (fields.next(), fields.next()) else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`AideCommentResponse` needs a struct with one field, which it responds like",
+        ));
+    };
+
+    let status = match input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("status"))
+    {
+        Some(attr) => match comment::ungroup(&attr.parse_args::<Expr>()?) {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(code),
+                ..
+            }) => {
+                let code = code.base10_parse::<u16>()?;
+                if !(100..=599).contains(&code) {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        format!("invalid status `{code}`"),
+                    ));
+                }
+                Some(code)
+            }
+            expr => return Err(syn::Error::new_spanned(expr, "expected a status code")),
+        },
+        None => None,
+    };
+
+    let description = comment::doc_lines(&input.attrs)?
+        .iter()
+        .map(|line| line.text.trim())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_owned();
+
+    let field_ty = &field.ty;
+    // a status replaces the ones the field would respond with
+    let inferred = match status {
+        Some(code) => quote! {
+            Self::operation_response(ctx, operation)
+                .map(|response| vec![(Some(#code), response)])
+                .unwrap_or_default()
+        },
+        None => quote! {
+            let mut responses = <#field_ty as __aide::OperationOutput>::inferred_responses(ctx, operation);
+            for (_, response) in &mut responses {
+                response.description = #description.to_owned();
+            }
+            responses
+        },
+    };
+
+    let aide = Args::default().crate_path("aide")?;
+    let ident = &input.ident;
+    let mut generics = input.generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote!(#field_ty: __aide::OperationOutput));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        const _: () = {
+            use #aide as __aide;
+
+            impl #impl_generics __aide::OperationOutput for #ident #ty_generics #where_clause {
+                type Inner = <#field_ty as __aide::OperationOutput>::Inner;
+
+                fn operation_response(
+                    ctx: &mut __aide::gen::GenContext,
+                    operation: &mut __aide::openapi::Operation,
+                ) -> Option<__aide::openapi::Response> {
+                    let mut response = <#field_ty as __aide::OperationOutput>::operation_response(ctx, operation)
+                        .unwrap_or_default();
+                    response.description = #description.to_owned();
+                    Some(response)
+                }
+
+                fn inferred_responses(
+                    ctx: &mut __aide::gen::GenContext,
+                    operation: &mut __aide::openapi::Operation,
+                ) -> Vec<(Option<u16>, __aide::openapi::Response)> {
+                    #inferred
+                }
+            }
+        };
+    })
+}
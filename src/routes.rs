@@ -0,0 +1,189 @@
+//! The `aidecomment_routes!` macro, which builds a router from a table of
+//! routes that are documented where they are declared, and the `documented!`
+//! macro, which routes handlers with the transforms of their documentation.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::{parse_quote, Attribute, Expr, ExprPath, Ident, LitStr, Signature, Token, Visibility};
+
+use crate::args::{Args, METHODS};
+use crate::comment;
+use crate::{handler, own_args, struct_path, Owner};
+
+/// A route like `GET "/users/{id}" => get_user`, along with its doc comment.
+struct Route {
+    attrs: Vec<Attribute>,
+    method: Ident,
+    path: LitStr,
+    handler: Expr,
+}
+
+impl Parse for Route {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let method = input.call(Ident::parse_any)?;
+        if !METHODS.contains(&method.to_string().to_lowercase().as_str()) {
+            return Err(syn::Error::new_spanned(
+                &method,
+                format!("expected one of {}", METHODS.join(", ").to_uppercase()),
+            ));
+        }
+        let path = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let handler = input.parse()?;
+
+        Ok(Route {
+            attrs,
+            method,
+            path,
+            handler,
+        })
+    }
+}
+
+/// Generates an `ApiRouter` with a route for each path, whose methods are
+/// documented by the `docs` functions of structs that are generated the way
+/// `transform_only` would.
+pub(crate) fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let routes = Punctuated::<Route, Token![,]>::parse_terminated.parse2(input)?;
+
+    let mut items = Vec::new();
+    // the methods of a path are routed together, in the order the path is
+    // first declared
+    let mut paths = Vec::<(String, Vec<TokenStream>)>::new();
+    for (index, mut route) in routes.into_iter().enumerate() {
+        // the handler is named after the function it calls, for the
+        // operation id
+        let name = match comment::ungroup(&route.handler) {
+            Expr::Path(ExprPath { path, .. }) => path.segments.last().map(|s| s.ident.clone()),
+            _ => None,
+        }
+        .unwrap_or_else(|| Ident::new("handler", Span::call_site()));
+        let mut sig: Signature = parse_quote!(fn #name());
+
+        let mut args =
+            own_args(&TokenStream::new(), &mut route.attrs, &sig, false)?.unwrap_or_default();
+        check(&args)?;
+        let struct_name = format_ident!("__AideCommentRoute{}", index, span = Span::mixed_site());
+        args.struct_name = Some(struct_name.clone());
+        args.transform_only = true;
+
+        let docs = handler(
+            args,
+            &route.attrs,
+            &Visibility::Inherited,
+            &mut sig,
+            Owner::Function(None),
+        )?;
+        items.push(docs);
+
+        let method = format_ident!("{}_with", route.method.to_string().to_lowercase());
+        let handler = &route.handler;
+        let routed = quote! {
+            #method(#handler, #struct_name::docs)
+        };
+
+        let path = axum_path(&route.path.value());
+        match paths.iter_mut().find(|(existing, _)| *existing == path) {
+            Some((_, methods)) => methods.push(routed),
+            None => paths.push((path, vec![routed])),
+        }
+    }
+
+    let aide = Args::default().crate_path("aide")?;
+    let routes = paths.iter().map(|(path, methods)| {
+        quote! {
+            .api_route(#path, #aide::axum::routing::#(#methods).*)
+        }
+    });
+
+    Ok(quote! {
+        {
+            #(#items)*
+            #aide::axum::ApiRouter::new() #(#routes)*
+        }
+    })
+}
+
+/// Checks that the arguments of a route aren't the ones that the macro
+/// decides instead.
+fn check(args: &Args) -> syn::Result<()> {
+    let decided = [
+        ("struct_name", args.struct_name.is_some()),
+        ("attach", args.attach.is_some()),
+        ("method", !args.methods.is_empty()),
+        ("cfg", args.cfg.is_some()),
+        ("transform_only", args.transform_only),
+    ];
+    match decided.iter().find(|(_, is_set)| *is_set) {
+        Some((name, _)) => Err(syn::Error::new(
+            Span::call_site(),
+            format!("`{name}` can't be used in `aidecomment_routes!`"),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Converts the path parameters of OpenAPI like `{id}` and `{*rest}` to the
+/// ones of axum like `:id` and `*rest`.
+fn axum_path(path: &str) -> String {
+    path.split('/')
+        .map(
+            |segment| match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+                Some(name) if name.starts_with('*') => name.to_owned(),
+                Some(name) => format!(":{name}"),
+                None => segment.to_owned(),
+            },
+        )
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// A method and the handler it is routed to, like `get get_user`.
+struct Documented {
+    method: Ident,
+    handler: syn::Path,
+}
+
+impl Parse for Documented {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let method = input.call(Ident::parse_any)?;
+        // the methods are written in any case, like in `aidecomment_routes!`
+        let lowercase = method.to_string().to_lowercase();
+        if !METHODS.contains(&lowercase.as_str()) {
+            return Err(syn::Error::new_spanned(
+                &method,
+                format!("expected one of {}", METHODS.join(", ")),
+            ));
+        }
+        let method = Ident::new(&lowercase, method.span());
+        let handler = input.parse()?;
+
+        Ok(Documented { method, handler })
+    }
+}
+
+/// Generates an `ApiMethodRouter` that routes each method to its handler
+/// along with the `docs` function of the handler's struct.
+pub(crate) fn documented(input: TokenStream) -> syn::Result<TokenStream> {
+    let routes = Punctuated::<Documented, Token![,]>::parse_terminated.parse2(input)?;
+    if routes.is_empty() {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "expected a method and a handler, like `get get_user`",
+        ));
+    }
+
+    let routed = routes.into_iter().map(|route| {
+        let method = format_ident!("{}_with", route.method);
+        let docs = struct_path(route.handler.clone());
+        let handler = route.handler;
+        quote!(#method(#handler, #docs::docs))
+    });
+
+    let aide = Args::default().crate_path("aide")?;
+    Ok(quote!(#aide::axum::routing::#(#routed).*))
+}
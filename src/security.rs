@@ -0,0 +1,178 @@
+//! The `AideCommentSecurityScheme` derive, which describes a security scheme
+//! with the doc comment of a struct that stands for it.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::punctuated::Punctuated;
+use syn::{Data, DeriveInput, Meta, Token};
+
+use crate::args::{self, Args};
+use crate::tag;
+
+/// Generates the name of the scheme and functions that add it to the
+/// components of the document.
+pub(crate) fn derive(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Struct(_) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`AideCommentSecurityScheme` can only be derived for structs",
+        ));
+    };
+    let Some(attr) = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("scheme"))
+    else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`AideCommentSecurityScheme` needs a `#[scheme(...)]` attribute, like `#[scheme(bearer)]`",
+        ));
+    };
+
+    let description = tag::description(&input.attrs)?;
+    let description = if description.is_empty() {
+        quote!(None)
+    } else {
+        quote!(Some(#description.to_owned()))
+    };
+
+    let mut name = None;
+    let mut scheme = None;
+    for meta in attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)? {
+        let path = meta.path();
+        if path.is_ident("name") {
+            name = Some(args::string(&meta)?);
+            continue;
+        }
+        if scheme.is_some() {
+            return Err(syn::Error::new_spanned(
+                &meta,
+                "the scheme is already given",
+            ));
+        }
+        scheme = Some(if path.is_ident("bearer") {
+            let mut format = None;
+            if let Meta::List(list) = &meta {
+                for meta in list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)? {
+                    if !meta.path().is_ident("format") {
+                        return Err(args::unknown(meta.path(), "`format`"));
+                    }
+                    format = Some(args::string(&meta)?);
+                }
+            }
+            let format = match format {
+                Some(format) => quote!(Some(#format.to_owned())),
+                None => quote!(None),
+            };
+            quote! {
+                __aide::openapi::SecurityScheme::Http {
+                    scheme: "bearer".to_owned(),
+                    bearer_format: #format,
+                    description: #description,
+                    extensions: ::core::default::Default::default(),
+                }
+            }
+        } else if path.is_ident("basic") {
+            meta.require_path_only()?;
+            quote! {
+                __aide::openapi::SecurityScheme::Http {
+                    scheme: "basic".to_owned(),
+                    bearer_format: None,
+                    description: #description,
+                    extensions: ::core::default::Default::default(),
+                }
+            }
+        } else if path.is_ident("api_key") {
+            let locations = meta
+                .require_list()?
+                .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+            let mut locations = locations.iter();
+            let (Some(location), None) = (locations.next(), locations.next()) else {
+                return Err(syn::Error::new_spanned(
+                    &meta,
+                    "`api_key` needs one of `header`, `query`, or `cookie`, like `api_key(header = \"X-API-Key\")`",
+                ));
+            };
+            let key = args::string(location)?;
+            let location = match location.path() {
+                path if path.is_ident("header") => quote!(Header),
+                path if path.is_ident("query") => quote!(Query),
+                path if path.is_ident("cookie") => quote!(Cookie),
+                path => return Err(args::unknown(path, "`header`, `query`, or `cookie`")),
+            };
+            quote! {
+                __aide::openapi::SecurityScheme::ApiKey {
+                    location: __aide::openapi::ApiKeyLocation::#location,
+                    name: #key.to_owned(),
+                    description: #description,
+                    extensions: ::core::default::Default::default(),
+                }
+            }
+        } else if path.is_ident("openid_connect") {
+            let url = meta
+                .require_list()?
+                .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+            let Some(url) = url.iter().find(|meta| meta.path().is_ident("url")) else {
+                return Err(syn::Error::new_spanned(
+                    &meta,
+                    "`openid_connect` needs a `url`",
+                ));
+            };
+            let url = args::string(url)?;
+            quote! {
+                __aide::openapi::SecurityScheme::OpenIdConnect {
+                    open_id_connect_url: #url.to_owned(),
+                    description: #description,
+                    extensions: ::core::default::Default::default(),
+                }
+            }
+        } else if path.is_ident("mutual_tls") {
+            meta.require_path_only()?;
+            quote! {
+                __aide::openapi::SecurityScheme::MutualTls {
+                    description: #description,
+                    extensions: ::core::default::Default::default(),
+                }
+            }
+        } else {
+            return Err(args::unknown(
+                path,
+                "`name`, `bearer`, `basic`, `api_key`, `openid_connect`, or `mutual_tls`",
+            ));
+        });
+    }
+    let Some(scheme) = scheme else {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "expected a scheme, like `bearer`, `basic`, or `api_key(header = \"...\")`",
+        ));
+    };
+    let name = name.unwrap_or_else(|| tag::snake_case(&input.ident.unraw().to_string()));
+
+    let aide = Args::default().crate_path("aide")?;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        const _: () = {
+            use #aide as __aide;
+
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// The name of the scheme, for `security("...")`.
+                pub const NAME: &'static str = #name;
+
+                /// The scheme along with its description.
+                pub fn security_scheme() -> __aide::openapi::SecurityScheme {
+                    #scheme
+                }
+
+                /// Adds the scheme to the components of the document, for
+                /// `finish_api_with`.
+                pub fn register(api: __aide::transform::TransformOpenApi<'_>) -> __aide::transform::TransformOpenApi<'_> {
+                    api.security_scheme(Self::NAME, Self::security_scheme())
+                }
+            }
+        };
+    })
+}
@@ -0,0 +1,174 @@
+//! The `aidecomment_tag!` macro, which describes a tag of the document with
+//! a doc comment.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Ident, Meta, Token, Visibility};
+
+use crate::args::{self, Args};
+use crate::comment;
+
+/// A unit struct that stands for a tag, like `pub struct Users;`.
+struct TagDef {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    ident: Ident,
+}
+
+impl Parse for TagDef {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![struct]>()?;
+        let ident = input.parse()?;
+        input.parse::<Token![;]>()?;
+
+        Ok(TagDef { attrs, vis, ident })
+    }
+}
+
+/// Generates a struct for each tag, with its name and functions that add it
+/// to the document.
+pub(crate) fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let parser = |input: ParseStream| {
+        let mut tags = Vec::new();
+        while !input.is_empty() {
+            tags.push(input.parse::<TagDef>()?);
+        }
+        Ok(tags)
+    };
+    let tags = parser.parse2(input)?;
+
+    let tags = tags
+        .into_iter()
+        .map(|tag| {
+            let mut name = None;
+            let mut external_docs = None;
+            let mut attrs = Vec::new();
+            for attr in tag.attrs {
+                if !attr.path().is_ident("aidecomment") {
+                    attrs.push(attr);
+                    continue;
+                }
+                let metas =
+                    attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+                for meta in metas {
+                    let path = meta.path();
+                    if path.is_ident("name") {
+                        name = Some(args::string(&meta)?);
+                    } else if path.is_ident("external_docs") {
+                        external_docs = Some(args::link(&meta)?);
+                    } else {
+                        return Err(args::unknown(path, "`name` or `external_docs`"));
+                    }
+                }
+            }
+
+            let name = name.unwrap_or_else(|| snake_case(&tag.ident.unraw().to_string()));
+            let description = description(&attrs)?;
+            tag_struct(
+                &attrs,
+                &tag.vis,
+                &tag.ident,
+                &name,
+                &description,
+                external_docs,
+            )
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote!(#(#tags)*))
+}
+
+/// The description of a tag, which is its whole doc comment.
+pub(crate) fn description(attrs: &[Attribute]) -> syn::Result<String> {
+    Ok(comment::doc_lines(attrs)?
+        .iter()
+        .map(|line| line.text.trim())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_owned())
+}
+
+/// Generates the struct that stands for a tag, with its name and functions
+/// that add it to the document.
+pub(crate) fn tag_struct(
+    attrs: &[Attribute],
+    vis: &Visibility,
+    ident: &Ident,
+    name: &str,
+    description: &str,
+    external_docs: Option<(String, Option<String>)>,
+) -> syn::Result<TokenStream> {
+    let aide = Args::default().crate_path("aide")?;
+    let description = if description.is_empty() {
+        quote!(None)
+    } else {
+        quote!(Some(#description.to_owned()))
+    };
+    let external_docs = match external_docs {
+        Some((url, description)) => {
+            let description = match description {
+                Some(description) => quote!(Some(#description.to_owned())),
+                None => quote!(None),
+            };
+            quote! {
+                Some(__aide::openapi::ExternalDocumentation {
+                    description: #description,
+                    url: #url.to_owned(),
+                    extensions: ::core::default::Default::default(),
+                })
+            }
+        }
+        None => quote!(None),
+    };
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis struct #ident;
+
+        const _: () = {
+            use #aide as __aide;
+
+            impl #ident {
+                /// The name of the tag, for `tag = "..."`.
+                pub const NAME: &'static str = #name;
+
+                /// The tag along with its description.
+                pub fn tag() -> __aide::openapi::Tag {
+                    __aide::openapi::Tag {
+                        name: Self::NAME.to_owned(),
+                        description: #description,
+                        external_docs: #external_docs,
+                        extensions: ::core::default::Default::default(),
+                    }
+                }
+
+                /// Adds the tag to the document, for `finish_api_with`.
+                pub fn register(api: __aide::transform::TransformOpenApi<'_>) -> __aide::transform::TransformOpenApi<'_> {
+                    api.tag(Self::tag())
+                }
+            }
+        };
+    })
+}
+
+/// Converts a name like `UserAccounts` to `user_accounts`.
+pub(crate) fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (index, c) in name.char_indices() {
+        if c.is_uppercase() {
+            if index > 0 && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
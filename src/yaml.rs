@@ -0,0 +1,389 @@
+//! A parser for the subset of YAML that is useful in front matter.
+//!
+//! This supports block mappings and sequences, `|` and `>` block scalars,
+//! flow sequences and mappings like `[a, b]` and `{a: b}`, quoted and plain
+//! scalars, and `#` comments. Anchors, tags, and multiple documents are not
+//! supported.
+
+use serde_json::{Map, Value};
+
+struct YamlLine<'a> {
+    indent: usize,
+    text: &'a str,
+    raw: &'a str,
+}
+
+/// Parses a YAML document into JSON.
+pub(crate) fn parse(source: &str) -> Result<Value, String> {
+    let lines = source
+        .lines()
+        .map(|raw| {
+            let text = raw.trim_start();
+            YamlLine {
+                indent: raw.len() - text.len(),
+                text: text.trim_end(),
+                raw,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut parser = Parser { lines, pos: 0 };
+    parser.skip_blank();
+    let Some(indent) = parser.peek().map(|line| line.indent) else {
+        return Ok(Value::Null);
+    };
+
+    let value = parser.block(indent)?;
+
+    parser.skip_blank();
+    match parser.peek() {
+        Some(line) => Err(format!("unexpected `{}`", line.text)),
+        None => Ok(value),
+    }
+}
+
+struct Parser<'a> {
+    lines: Vec<YamlLine<'a>>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_blank(&mut self) {
+        while self
+            .lines
+            .get(self.pos)
+            .is_some_and(|line| line.text.is_empty() || line.text.starts_with('#'))
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<&YamlLine<'a>> {
+        self.lines.get(self.pos)
+    }
+
+    /// Parses the mapping, sequence, or scalar starting at the current line.
+    fn block(&mut self, indent: usize) -> Result<Value, String> {
+        let line = &self.lines[self.pos];
+        if is_sequence_item(line.text) {
+            self.sequence(indent)
+        } else if split_key(line.text).is_some() {
+            self.mapping(indent)
+        } else {
+            self.pos += 1;
+            scalar(line.text)
+        }
+    }
+
+    fn mapping(&mut self, indent: usize) -> Result<Value, String> {
+        let mut map = Map::new();
+
+        loop {
+            self.skip_blank();
+            let Some(line) = self.peek() else {
+                break;
+            };
+            if line.indent < indent || is_sequence_item(line.text) {
+                break;
+            }
+            if line.indent > indent {
+                return Err(format!("unexpected indentation of `{}`", line.text));
+            }
+
+            let text = line.text;
+            let Some((key, value)) = split_key(text) else {
+                return Err(format!("expected `key: value`, found `{text}`"));
+            };
+            let key = unquote(key)?;
+            self.pos += 1;
+
+            let value = self.value(indent, value, true)?;
+            map.insert(key, value);
+        }
+
+        Ok(Value::Object(map))
+    }
+
+    fn sequence(&mut self, indent: usize) -> Result<Value, String> {
+        let mut items = Vec::new();
+
+        loop {
+            self.skip_blank();
+            let Some(line) = self.peek() else {
+                break;
+            };
+            if line.indent != indent || !is_sequence_item(line.text) {
+                break;
+            }
+
+            let rest = line.text[1..].trim_start();
+            if split_key(rest).is_some() {
+                // a mapping that starts on the same line as the `-`, whose
+                // other keys line up with the first one
+                let offset = line.text.len() - rest.len();
+                self.lines[self.pos].indent += offset;
+                self.lines[self.pos].text = rest;
+                items.push(self.mapping(indent + offset)?);
+            } else {
+                self.pos += 1;
+                items.push(self.value(indent, rest, false)?);
+            }
+        }
+
+        Ok(Value::Array(items))
+    }
+
+    /// Parses the value after a key or `-` on a line with the given indent,
+    /// which may continue on the following lines.
+    fn value(&mut self, indent: usize, text: &str, in_mapping: bool) -> Result<Value, String> {
+        let text = strip_comment(text);
+        if let Some(style) = text.strip_prefix(['|', '>']) {
+            if !style.chars().all(|c| matches!(c, '-' | '+')) {
+                return Err(format!("unsupported block scalar `{text}`"));
+            }
+            return Ok(Value::String(self.block_scalar(
+                indent,
+                text.starts_with('>'),
+                style,
+            )));
+        }
+        if !text.is_empty() {
+            return scalar(text);
+        }
+
+        self.skip_blank();
+        match self.peek() {
+            Some(line) if line.indent > indent => {
+                let indent = line.indent;
+                self.block(indent)
+            }
+            // a sequence can line up with the key it belongs to
+            Some(line) if in_mapping && line.indent == indent && is_sequence_item(line.text) => {
+                self.sequence(indent)
+            }
+            _ => Ok(Value::Null),
+        }
+    }
+
+    fn block_scalar(&mut self, indent: usize, folded: bool, chomping: &str) -> String {
+        let mut lines = Vec::new();
+        let mut content_indent = None;
+
+        while let Some(line) = self.lines.get(self.pos) {
+            if !line.text.is_empty() && line.indent <= indent {
+                break;
+            }
+            if line.text.is_empty() {
+                lines.push("");
+            } else {
+                let content_indent = *content_indent.get_or_insert(line.indent);
+                lines.push(
+                    line.raw
+                        .get(content_indent..)
+                        .unwrap_or(line.text)
+                        .trim_end(),
+                );
+            }
+            self.pos += 1;
+        }
+
+        let trailing = lines
+            .iter()
+            .rev()
+            .take_while(|line| line.is_empty())
+            .count();
+        lines.truncate(lines.len() - trailing);
+
+        let mut text = if folded {
+            let mut text = String::new();
+            for line in &lines {
+                if line.is_empty() {
+                    text.push('\n');
+                } else {
+                    if !text.is_empty() && !text.ends_with('\n') {
+                        text.push(' ');
+                    }
+                    text.push_str(line);
+                }
+            }
+            text
+        } else {
+            lines.join("\n")
+        };
+
+        match chomping {
+            "-" => {}
+            "+" => text.push_str(&"\n".repeat(trailing + 1)),
+            _ => text.push('\n'),
+        }
+
+        text
+    }
+}
+
+fn is_sequence_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+/// Splits a `key: value` line, taking quoted keys into account.
+fn split_key(text: &str) -> Option<(&str, &str)> {
+    if text.starts_with(['[', '{']) {
+        return None;
+    }
+
+    let start = match text.chars().next()? {
+        quote @ ('"' | '\'') => text[1..].find(quote)? + 2,
+        _ => 0,
+    };
+
+    let colon = text[start..]
+        .match_indices(':')
+        .map(|(index, _)| start + index)
+        .find(|&index| text[index + 1..].is_empty() || text[index + 1..].starts_with(' '))?;
+
+    Some((text[..colon].trim(), text[colon + 1..].trim()))
+}
+
+/// Removes a `#` comment after a value, which has to be preceded by a space.
+fn strip_comment(text: &str) -> &str {
+    let mut quote = None;
+    for (index, c) in text.char_indices() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(open)) if c == open => quote = None,
+            ('#', None) if index == 0 || text[..index].ends_with(' ') => {
+                return text[..index].trim_end();
+            }
+            _ => {}
+        }
+    }
+    text.trim_end()
+}
+
+/// Parses a scalar or flow collection on a single line.
+fn scalar(text: &str) -> Result<Value, String> {
+    let text = strip_comment(text);
+    if text.starts_with(['[', '{']) {
+        let (value, rest) = flow(text)?;
+        if !rest.trim().is_empty() {
+            return Err(format!("unexpected `{}`", rest.trim()));
+        }
+        return Ok(value);
+    }
+    if text.starts_with(['"', '\'']) {
+        return unquote(text).map(Value::String);
+    }
+
+    Ok(plain(text))
+}
+
+/// Parses a plain scalar, which is a string unless it looks like something
+/// else.
+fn plain(text: &str) -> Value {
+    match text {
+        "" | "~" | "null" | "Null" | "NULL" => Value::Null,
+        "true" | "True" | "TRUE" => Value::Bool(true),
+        "false" | "False" | "FALSE" => Value::Bool(false),
+        _ => match serde_json::from_str::<serde_json::Number>(text) {
+            Ok(number) => Value::Number(number),
+            Err(_) => Value::String(text.to_owned()),
+        },
+    }
+}
+
+/// Parses a flow collection at the start of the text, returning it along with
+/// the rest of the text.
+fn flow(text: &str) -> Result<(Value, &str), String> {
+    let (close, is_mapping) = match text.chars().next() {
+        Some('[') => (']', false),
+        Some('{') => ('}', true),
+        _ => return Err(format!("expected `[` or `{{`, found `{text}`")),
+    };
+
+    let mut rest = text[1..].trim_start();
+    let mut items = Vec::new();
+    let mut map = Map::new();
+
+    loop {
+        if let Some(after) = rest.strip_prefix(close) {
+            rest = after;
+            break;
+        }
+
+        let (key, after) = if is_mapping {
+            let (key, after) = flow_item(rest, &[':'])?;
+            let after = after
+                .strip_prefix(':')
+                .ok_or_else(|| format!("expected `:` after `{key}` in `{text}`"))?;
+            (Some(key), after.trim_start())
+        } else {
+            (None, rest)
+        };
+
+        let (value, after) = if after.starts_with(['[', '{']) {
+            flow(after)?
+        } else {
+            let (item, after) = flow_item(after, &[',', close])?;
+            let value = if item.starts_with(['"', '\'']) {
+                Value::String(unquote(&item)?)
+            } else {
+                plain(&item)
+            };
+            (value, after)
+        };
+
+        match key {
+            Some(key) => {
+                map.insert(unquote(&key)?, value);
+            }
+            None => items.push(value),
+        }
+
+        rest = after.trim_start();
+        if let Some(after) = rest.strip_prefix(',') {
+            rest = after.trim_start();
+        } else if !rest.starts_with(close) {
+            return Err(format!("unterminated `{text}`"));
+        }
+    }
+
+    let value = if is_mapping {
+        Value::Object(map)
+    } else {
+        Value::Array(items)
+    };
+
+    Ok((value, rest))
+}
+
+/// Takes a scalar in a flow collection up to one of the given delimiters.
+fn flow_item<'t>(text: &'t str, delimiters: &[char]) -> Result<(String, &'t str), String> {
+    if let Some(quote @ ('"' | '\'')) = text.chars().next() {
+        let end = text[1..]
+            .find(quote)
+            .ok_or_else(|| format!("unterminated string in `{text}`"))?;
+        return Ok((text[..end + 2].to_owned(), text[end + 2..].trim_start()));
+    }
+
+    match text.find(delimiters) {
+        Some(end) => Ok((text[..end].trim().to_owned(), &text[end..])),
+        None => Err(format!("unterminated `{text}`")),
+    }
+}
+
+/// Removes the quotes around a string, handling escapes, or returns it as-is
+/// if it isn't quoted.
+fn unquote(text: &str) -> Result<String, String> {
+    if let Some(inner) = text.strip_prefix('\'') {
+        let inner = inner
+            .strip_suffix('\'')
+            .ok_or_else(|| format!("unterminated string `{text}`"))?;
+        return Ok(inner.replace("''", "'"));
+    }
+
+    if text.starts_with('"') {
+        return serde_json::from_str(text).map_err(|_| format!("invalid string `{text}`"));
+    }
+
+    Ok(text.to_owned())
+}
@@ -0,0 +1,647 @@
+//! The arguments of the attribute.
+
+// the types only exist for what is derived or documented from them
+#![allow(dead_code)]
+
+mod common;
+
+#[test]
+fn tags_and_auto_operation_id() {
+    use aidecomment::aidecomment;
+    /// Gets a user
+    #[aidecomment(tag = "users", tag = "admin", auto_operation_id)]
+    async fn get_user() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(get_user));
+    assert_eq!(operation.tags, ["users", "admin"]);
+    assert_eq!(operation.operation_id.as_deref(), Some("get_user"));
+}
+
+#[test]
+fn module_tag() {
+    mod user_accounts {
+        use aidecomment::aidecomment;
+        /// Gets a user
+        #[aidecomment(module_tag(case = "title"))]
+        pub async fn get_user() {}
+    }
+    use aide::axum::routing::get;
+    let operation = common::operation(get(user_accounts::get_user));
+    assert_eq!(operation.tags, ["User Accounts"]);
+}
+
+#[test]
+// the debug output is reported as a deprecation warning
+#[allow(deprecated)]
+fn debug() {
+    use aidecomment::aidecomment;
+    /// Gets a user
+    #[aidecomment(debug)]
+    async fn get_user() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(get_user));
+    assert_eq!(operation.summary.as_deref(), Some("Gets a user"));
+}
+
+#[test]
+fn operation_id_over_doc_comment() {
+    use aidecomment::aidecomment;
+    /// Gets a user
+    ///
+    /// @operationId fetchUser
+    #[aidecomment(jsdoc, operation_id = "getUser")]
+    async fn fetch_user() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(fetch_user));
+    assert_eq!(operation.operation_id.as_deref(), Some("getUser"));
+}
+
+#[test]
+fn summary_and_description() {
+    use aidecomment::aidecomment;
+    /// Looks up the user in the cache before hitting the database
+    ///
+    /// Callers must hold the connection lock.
+    #[aidecomment(
+        summary = "Gets a user",
+        description = "Gets the user with the given id."
+    )]
+    async fn get_user() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(get_user));
+    assert_eq!(operation.summary.as_deref(), Some("Gets a user"));
+    assert_eq!(
+        operation.description.as_deref(),
+        Some("Gets the user with the given id.")
+    );
+}
+
+#[test]
+fn summary_prefix() {
+    use aidecomment::aidecomment;
+    /// Bans a user
+    #[aidecomment(summary_prefix = "[Admin] ")]
+    async fn ban_user() {}
+    use aide::axum::routing::post;
+    let operation = common::operation(post(ban_user));
+    assert_eq!(operation.summary.as_deref(), Some("[Admin] Bans a user"));
+}
+
+#[test]
+fn summary_mode() {
+    use aidecomment::aidecomment;
+    /// Gets a user. The user can be looked up by id or by name, e.g. `alice`.
+    ///
+    /// Users that were deleted are not found.
+    #[aidecomment(summary_mode = "first_sentence")]
+    async fn get_user() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(get_user));
+    assert_eq!(operation.summary.as_deref(), Some("Gets a user."));
+    assert_eq!(operation.description.as_deref(), Some("The user can be looked up by id or by name, e.g. `alice`.\n\n Users that were deleted are not found."));
+}
+
+#[test]
+fn description_only() {
+    use aidecomment::aidecomment;
+    /// Exports every order of the account as CSV, which can take a while for
+    /// large accounts and is rate limited separately.
+    #[aidecomment(description_only)]
+    async fn export_orders() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(export_orders));
+    assert_eq!(operation.summary, None);
+    assert_eq!(operation.description.as_deref(), Some("Exports every order of the account as CSV, which can take a while for large accounts and is rate limited separately."));
+}
+
+#[test]
+fn method() {
+    use aide::axum::{routing::get, ApiRouter};
+    use aidecomment::aidecomment;
+
+    /// Downloads a file
+    #[aidecomment(method(HEAD, summary = "Checks the size of a file"))]
+    async fn download() {}
+
+    let router = ApiRouter::<()>::new().api_route_with(
+        "/",
+        get(download).head(download),
+        download_AideComment::methods,
+    );
+    let api = common::document(router);
+    let item = common::path_item(&api, "/");
+    assert_eq!(
+        item.get.unwrap().summary.as_deref(),
+        Some("Downloads a file")
+    );
+    assert_eq!(
+        item.head.unwrap().summary.as_deref(),
+        Some("Checks the size of a file")
+    );
+}
+
+#[test]
+fn audience() {
+    use aidecomment::aidecomment;
+    /// Refunds an order
+    #[aidecomment(audience = "partner")]
+    async fn refund_order() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(refund_order));
+    assert_eq!(operation.extensions["x-audience"], "partner");
+    assert!(!operation.extensions.contains_key("x-internal"));
+}
+
+#[test]
+fn summary_only() {
+    use aidecomment::aidecomment;
+    /// Checks that the service is up
+    ///
+    /// This is polled by the load balancer, so it shouldn't touch the database.
+    #[aidecomment(summary_only)]
+    async fn health() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(health));
+    assert_eq!(
+        operation.summary.as_deref(),
+        Some("Checks that the service is up")
+    );
+    assert_eq!(operation.description, None);
+}
+
+#[test]
+fn merge() {
+    use aide::{gen::GenContext, openapi::Operation, OperationInput};
+    use aidecomment::aidecomment;
+    use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+    struct Session;
+
+    impl OperationInput for Session {
+        fn operation_input(_: &mut GenContext, operation: &mut Operation) {
+            operation.description = Some("Requires a session.".to_owned());
+        }
+    }
+    #[async_trait]
+    impl<S: Send + Sync> FromRequestParts<S> for Session {
+        type Rejection = ();
+        async fn from_request_parts(_: &mut Parts, _: &S) -> Result<Self, ()> {
+            Ok(Session)
+        }
+    }
+
+    /// Logs out
+    ///
+    /// Ends the session.
+    #[aidecomment(merge = "append")]
+    async fn log_out(_: Session) {}
+    use aide::axum::routing::post;
+    let operation = common::operation(post(log_out));
+    assert_eq!(operation.summary.as_deref(), Some("Logs out"));
+    assert_eq!(
+        operation.description.as_deref(),
+        Some("Requires a session.\n\nEnds the session.")
+    );
+}
+
+#[test]
+fn hidden() {
+    use aidecomment::aidecomment;
+    /// Clears the caches
+    #[aidecomment(hidden)]
+    async fn clear_caches() {}
+    use aide::axum::routing::post;
+    let operation = common::operation(post(clear_caches));
+    assert_eq!(operation.extensions["x-internal"], true);
+}
+
+#[test]
+fn deprecated() {
+    use aidecomment::aidecomment;
+    /// Lists users
+    #[aidecomment(deprecated = "Use /v2/users instead.")]
+    async fn list_users() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(list_users));
+    assert!(operation.deprecated);
+    assert_eq!(
+        operation.description.as_deref(),
+        Some("**Deprecated:** Use /v2/users instead.")
+    );
+}
+
+#[test]
+fn security() {
+    use aidecomment::aidecomment;
+    /// Lists users
+    #[aidecomment(security("bearer_auth", scopes("users:read")), security("api_key"))]
+    async fn list_users() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(list_users));
+    assert_eq!(operation.security.len(), 2);
+    assert_eq!(operation.security[0]["bearer_auth"], ["users:read"]);
+    assert!(operation.security[1]["api_key"].is_empty());
+}
+
+#[test]
+fn auth() {
+    use aidecomment::aidecomment;
+    /// Gets the current user
+    #[aidecomment(auth = "bearer")]
+    async fn me() {}
+
+    /// Checks that the service is up
+    #[aidecomment(auth = "none")]
+    async fn health() {}
+    use aide::axum::{routing::get, ApiRouter};
+    let api = common::document(
+        ApiRouter::<()>::new()
+            .api_route("/me", get(me))
+            .api_route("/health", get(health)),
+    );
+    let operation = common::path_item(&api, "/me").get.unwrap();
+    assert!(operation.security[0].contains_key("bearer_auth"));
+    assert_eq!(
+        operation.description.as_deref(),
+        Some("Requires a bearer token in the `Authorization` header.")
+    );
+    let operation = common::path_item(&api, "/health").get.unwrap();
+    assert_eq!(operation.security.len(), 1);
+    assert!(operation.security[0].is_empty());
+}
+
+#[test]
+fn responses() {
+    use aidecomment::aidecomment;
+    /// Gets a user
+    #[aidecomment(
+        response(status = 404, description = "The user doesn't exist"),
+        response(status = "5XX", description = "The database is unavailable")
+    )]
+    async fn get_user() {}
+    use aide::{axum::routing::get, openapi::StatusCode};
+    let operation = common::operation(get(get_user));
+    let responses = operation.responses.unwrap().responses;
+    assert_eq!(
+        responses[&StatusCode::Code(404)]
+            .as_item()
+            .unwrap()
+            .description,
+        "The user doesn't exist"
+    );
+    assert_eq!(
+        responses[&StatusCode::Range(5)]
+            .as_item()
+            .unwrap()
+            .description,
+        "The database is unavailable"
+    );
+}
+
+#[test]
+fn response_ty() {
+    use aide::axum::IntoApiResponse;
+    use aidecomment::aidecomment;
+    use axum::Json;
+    /// Gets the names of the users
+    ///
+    /// # Responses
+    ///
+    /// - 200: The names, sorted alphabetically
+    /// - 404: There are no users
+    #[aidecomment(response_ty = Json<Vec<String>>)]
+    async fn user_names() -> impl IntoApiResponse {
+        Json(vec!["alice".to_owned()])
+    }
+    use aide::axum::routing::get;
+    aide::gen::on_error(|error| panic!("{error}"));
+    let operation = common::operation(get(user_names));
+    assert_eq!(
+        common::response(&operation, 404).description,
+        "There are no users"
+    );
+    let response = common::response(&operation, 200);
+    assert!(response.content["application/json"].schema.is_some());
+}
+
+#[test]
+fn error() {
+    use aidecomment::{aidecomment, ErrorResponses};
+    use axum::{
+        http::StatusCode,
+        response::{IntoResponse, Response},
+    };
+    #[derive(ErrorResponses)]
+    enum UserError {
+        /// The user doesn't exist
+        #[status(404)]
+        NotFound,
+        /// The user is banned
+        #[status(403)]
+        Banned,
+    }
+    impl IntoResponse for UserError {
+        fn into_response(self) -> Response {
+            StatusCode::NOT_FOUND.into_response()
+        }
+    }
+    impl aide::OperationOutput for UserError {
+        type Inner = ();
+    }
+
+    /// Gets a user
+    #[aidecomment(error = UserError)]
+    async fn get_user() -> Result<(), UserError> {
+        Err(UserError::NotFound)
+    }
+    use aide::{axum::routing::get, openapi::StatusCode as Code};
+    let operation = common::operation(get(get_user));
+    let responses = operation.responses.unwrap().responses;
+    assert_eq!(
+        responses[&Code::Code(404)].as_item().unwrap().description,
+        "The user doesn't exist"
+    );
+    assert_eq!(
+        responses[&Code::Code(403)].as_item().unwrap().description,
+        "The user is banned"
+    );
+}
+
+#[test]
+fn params() {
+    use aidecomment::{aidecomment, AideCommentParams};
+    use axum::extract::Query;
+    use schemars::JsonSchema;
+    use serde::Deserialize;
+
+    #[derive(Deserialize, JsonSchema, AideCommentParams)]
+    struct Search {
+        /// Words that the name of the user contains
+        ///
+        /// They are matched regardless of case.
+        name: String,
+    }
+
+    /// Searches users
+    #[aidecomment(params = Search)]
+    async fn search_users(Query(_search): Query<Search>) {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(search_users));
+    let parameter = operation.parameters[0]
+        .as_item()
+        .unwrap()
+        .parameter_data_ref();
+    assert_eq!(
+        parameter.description.as_deref(),
+        Some("Words that the name of the user contains\n\nThey are matched regardless of case.")
+    );
+}
+
+#[test]
+fn external_docs() {
+    use aidecomment::aidecomment;
+    /// Gets a user
+    #[aidecomment(external_docs(
+        url = "https://developer.example.com/users",
+        description = "Users guide"
+    ))]
+    async fn get_user() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(get_user));
+    let external_docs = operation.external_docs.unwrap();
+    assert_eq!(external_docs.url, "https://developer.example.com/users");
+    assert_eq!(external_docs.description.as_deref(), Some("Users guide"));
+}
+
+#[test]
+fn consumes_and_produces() {
+    use aidecomment::{aidecomment, documented};
+    use axum::Json;
+    /// Imports users and exports the result
+    #[aidecomment(
+        transform_only,
+        consumes = "application/x-ndjson",
+        produces = "text/csv"
+    )]
+    async fn import_users(Json(users): Json<Vec<String>>) -> Json<Vec<String>> {
+        Json(users)
+    }
+
+    let operation = common::operation(documented!(post import_users));
+    let request_body = operation.request_body.as_ref().unwrap().as_item().unwrap();
+    assert_eq!(
+        request_body.content.keys().collect::<Vec<_>>(),
+        ["application/x-ndjson"]
+    );
+    assert!(request_body.content["application/x-ndjson"]
+        .schema
+        .is_some());
+    let response = common::response(&operation, 200);
+    assert_eq!(response.content.keys().collect::<Vec<_>>(), ["text/csv"]);
+}
+
+#[test]
+fn server() {
+    use aidecomment::aidecomment;
+    /// Uploads a file
+    #[aidecomment(server(url = "https://uploads.example.com", description = "Upload domain"))]
+    async fn upload() {}
+    use aide::axum::routing::post;
+    let operation = common::operation(post(upload));
+    assert_eq!(operation.servers[0].url, "https://uploads.example.com");
+    assert_eq!(
+        operation.servers[0].description.as_deref(),
+        Some("Upload domain")
+    );
+}
+
+#[test]
+fn extensions() {
+    use aidecomment::aidecomment;
+    /// Refunds a payment
+    #[aidecomment(extensions(x_owner = "payments-team", x_tier = 2, x_regions = ["eu", "us"]))]
+    async fn refund() {}
+    use aide::axum::routing::post;
+    let operation = common::operation(post(refund));
+    assert_eq!(operation.extensions["x-owner"], "payments-team");
+    assert_eq!(operation.extensions["x-tier"], 2);
+    assert_eq!(
+        operation.extensions["x-regions"],
+        serde_json::json!(["eu", "us"])
+    );
+}
+
+#[test]
+fn transform() {
+    use aide::transform::TransformOperation;
+    use aidecomment::aidecomment;
+
+    fn admin_only(operation: TransformOperation) -> TransformOperation {
+        operation.tag("admin").security_requirement("session")
+    }
+
+    /// Deletes a user
+    #[aidecomment(transform = admin_only)]
+    async fn delete_user() {}
+    use aide::axum::routing::delete;
+    let operation = common::operation(delete(delete_user));
+    assert_eq!(operation.summary.as_deref(), Some("Deletes a user"));
+    assert_eq!(operation.tags, ["admin"]);
+    assert!(operation.security[0].contains_key("session"));
+}
+
+#[test]
+fn struct_name() {
+    use aidecomment::aidecomment;
+    /// Gets a user
+    #[aidecomment(struct_name = "GetUserDocs")]
+    async fn get_user() {}
+
+    /// Gets an order
+    #[aidecomment(struct_name = "GetOrderDocs", transform_only)]
+    async fn get_order() {}
+
+    let _docs: GetUserDocs = GetUserDocs(());
+    use aide::axum::{
+        routing::{get, get_with},
+        ApiRouter,
+    };
+    let router = ApiRouter::<()>::new()
+        .api_route("/user", get(get_user))
+        .api_route("/order", get_with(get_order, GetOrderDocs::docs));
+    let api = common::document(router);
+    let operation = common::path_item(&api, "/order").get.unwrap();
+    assert_eq!(operation.summary.as_deref(), Some("Gets an order"));
+    assert_eq!(GetUserDocs::<()>::SUMMARY, "Gets a user");
+}
+
+mod handlers {
+    use aidecomment::aidecomment;
+
+    /// Gets a user
+    #[aidecomment(vis = "pub(crate)")]
+    async fn get_user() {}
+
+    pub fn router() -> aide::axum::ApiRouter {
+        aide::axum::ApiRouter::new().api_route("/", aide::axum::routing::get(get_user))
+    }
+}
+
+#[test]
+fn vis() {
+    // the struct can be reached from outside of the module of the handler
+    let _ = handlers::router();
+    assert_eq!(
+        crate::handlers::get_user_AideComment::<()>::SUMMARY,
+        "Gets a user"
+    );
+}
+
+#[test]
+fn attach() {
+    use aidecomment::aidecomment;
+    use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+    struct AdminUser;
+
+    #[async_trait]
+    impl<S: Send + Sync> FromRequestParts<S> for AdminUser {
+        // ...
+        type Rejection = ();
+        async fn from_request_parts(_: &mut Parts, _: &S) -> Result<Self, ()> {
+            Ok(AdminUser)
+        }
+    }
+
+    /// Lists the audit log
+    #[aidecomment(attach = AdminUser)]
+    async fn audit_log(_: AdminUser) {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(audit_log));
+    assert_eq!(operation.summary.as_deref(), Some("Lists the audit log"));
+}
+
+#[test]
+fn inherit() {
+    use aidecomment::aidecomment;
+    /// Lists users
+    ///
+    /// The users are sorted by name.
+    #[aidecomment]
+    async fn list_users() {}
+
+    /// Includes users that were deleted.
+    #[aidecomment(inherit = list_users)]
+    async fn list_all_users() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(list_all_users));
+    assert_eq!(operation.summary.as_deref(), Some("Lists users"));
+    assert_eq!(
+        operation.description.as_deref(),
+        Some("The users are sorted by name.\n\nIncludes users that were deleted.")
+    );
+}
+
+#[test]
+fn use_const() {
+    use aidecomment::aidecomment;
+    const PAGINATION: &str = concat!(
+        "Lists a page of results\n",
+        "\n",
+        "Pages have at most 100 items, and `next` links to the next one.",
+    );
+
+    /// Lists users
+    #[aidecomment(use_const = PAGINATION)]
+    async fn list_users() {}
+
+    #[aidecomment(use_const = PAGINATION)]
+    async fn list_orders() {}
+    use aide::axum::{routing::get, ApiRouter};
+    let api = common::document(
+        ApiRouter::<()>::new()
+            .api_route("/users", get(list_users))
+            .api_route("/orders", get(list_orders)),
+    );
+    let users = common::path_item(&api, "/users").get.unwrap();
+    assert_eq!(users.summary.as_deref(), Some("Lists users"));
+    assert_eq!(users.description.as_deref(), Some("Lists a page of results\n\nPages have at most 100 items, and `next` links to the next one."));
+    let orders = common::path_item(&api, "/orders").get.unwrap();
+    assert_eq!(orders.summary.as_deref(), Some("Lists a page of results"));
+    assert_eq!(
+        orders.description.as_deref(),
+        Some("Pages have at most 100 items, and `next` links to the next one.")
+    );
+}
+
+#[test]
+fn crate_paths() {
+    use aidecomment::aidecomment;
+    mod facade {
+        pub use aide;
+        pub use axum;
+    }
+
+    /// Gets a user
+    #[aidecomment(aide = facade::aide, axum = facade::axum)]
+    async fn get_user() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(get_user));
+    assert_eq!(operation.summary.as_deref(), Some("Gets a user"));
+}
+
+#[test]
+// this crate has no `docs` feature, which is the point
+#[allow(unexpected_cfgs)]
+fn cfg() {
+    use aidecomment::aidecomment;
+    /// Gets a user
+    #[aidecomment(cfg = "docs")]
+    async fn get_user(_id: u32) {}
+    // the feature isn't enabled, so the argument isn't wrapped
+    drop(get_user(1));
+    /// Lists users
+    #[aidecomment(cfg(all()))]
+    async fn list_users() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(list_users));
+    assert_eq!(operation.summary.as_deref(), Some("Lists users"));
+}
@@ -0,0 +1,68 @@
+//! Building the documents that the tests check.
+
+// each test file uses a different part of this
+#![allow(dead_code)]
+
+use aide::axum::routing::ApiMethodRouter;
+use aide::axum::ApiRouter;
+use aide::openapi::{OpenApi, Operation, Parameter, ParameterData, PathItem, Response, StatusCode};
+use aide::transform::TransformOpenApi;
+
+/// The document of a router.
+pub fn document<S: Clone + Send + Sync + 'static>(router: ApiRouter<S>) -> OpenApi {
+    let mut api = OpenApi::default();
+    let _ = router.finish_api(&mut api);
+    api
+}
+
+/// The document of a router, which `transform` changes as it is finished.
+pub fn document_with<S, F>(router: ApiRouter<S>, transform: F) -> OpenApi
+where
+    S: Clone + Send + Sync + 'static,
+    F: FnOnce(TransformOpenApi) -> TransformOpenApi,
+{
+    let mut api = OpenApi::default();
+    let _ = router.finish_api_with(&mut api, transform);
+    api
+}
+
+/// The path item of a document at `path`.
+pub fn path_item(api: &OpenApi, path: &str) -> PathItem {
+    let paths = api.paths.as_ref().expect("the document has no paths");
+    paths.paths[path]
+        .as_item()
+        .expect("the path item is a reference")
+        .clone()
+}
+
+/// The operation of a route, which is routed at `/` by itself.
+pub fn operation(route: ApiMethodRouter) -> Operation {
+    let api = document(ApiRouter::new().api_route("/", route));
+    let item = path_item(&api, "/");
+    let (_, operation) = item.iter().next().expect("the route has no operation");
+    operation.clone()
+}
+
+/// The response of an operation with a status code.
+pub fn response(operation: &Operation, code: u16) -> Response {
+    let responses = operation
+        .responses
+        .as_ref()
+        .expect("the operation has no responses");
+    responses.responses[&StatusCode::Code(code)]
+        .as_item()
+        .expect("the response is a reference")
+        .clone()
+}
+
+/// The data of a parameter of an operation.
+pub fn parameter(operation: &Operation, name: &str) -> ParameterData {
+    operation
+        .parameters
+        .iter()
+        .filter_map(|parameter| parameter.as_item())
+        .map(Parameter::parameter_data_ref)
+        .find(|parameter| parameter.name == name)
+        .unwrap_or_else(|| panic!("the operation has no `{name}` parameter"))
+        .clone()
+}
@@ -0,0 +1,155 @@
+//! The derives.
+
+// the types only exist for what is derived or documented from them
+#![allow(dead_code)]
+
+mod common;
+
+#[test]
+fn error_responses() {
+    use aidecomment::ErrorResponses;
+    #[derive(ErrorResponses)]
+    enum PaymentError {
+        /// The card was declined
+        #[status(402)]
+        Declined,
+        /// The card has expired
+        #[status(402)]
+        Expired,
+    }
+    assert_eq!(
+        PaymentError::AIDECOMMENT_RESPONSES,
+        [(402, "- The card was declined\n- The card has expired")]
+    );
+}
+
+#[test]
+fn responses() {
+    use aidecomment::{aidecomment, AideCommentResponses};
+    use axum::{
+        http::StatusCode,
+        response::{IntoResponse, Response},
+    };
+    #[derive(AideCommentResponses)]
+    enum OrderError {
+        /// The order doesn't exist
+        #[status(404)]
+        NotFound,
+        /// The order was already shipped
+        #[status(409)]
+        Shipped,
+    }
+    impl IntoResponse for OrderError {
+        fn into_response(self) -> Response {
+            StatusCode::NOT_FOUND.into_response()
+        }
+    }
+
+    /// Cancels an order
+    #[aidecomment]
+    async fn cancel_order() -> Result<(), OrderError> {
+        Err(OrderError::Shipped)
+    }
+    use aide::{axum::routing::post, openapi::StatusCode as Code};
+    let operation = common::operation(post(cancel_order));
+    let responses = operation.responses.unwrap().responses;
+    assert_eq!(
+        responses[&Code::Code(404)].as_item().unwrap().description,
+        "The order doesn't exist"
+    );
+    assert_eq!(
+        responses[&Code::Code(409)].as_item().unwrap().description,
+        "The order was already shipped"
+    );
+}
+
+#[test]
+fn response() {
+    use aidecomment::{aidecomment, AideCommentResponse};
+    use axum::{
+        http::StatusCode,
+        response::{IntoResponse, Response},
+        Json,
+    };
+
+    /// The resource was created
+    #[derive(AideCommentResponse)]
+    #[status(201)]
+    struct Created<T>(Json<T>);
+
+    impl<T: serde::Serialize> IntoResponse for Created<T> {
+        fn into_response(self) -> Response {
+            (StatusCode::CREATED, self.0).into_response()
+        }
+    }
+
+    /// Creates a user
+    #[aidecomment]
+    async fn create_user() -> Created<String> {
+        Created(Json("alice".to_owned()))
+    }
+    use aide::{axum::routing::post, openapi::StatusCode as Code};
+    let operation = common::operation(post(create_user));
+    let responses = operation.responses.unwrap().responses;
+    assert_eq!(
+        responses[&Code::Code(201)].as_item().unwrap().description,
+        "The resource was created"
+    );
+}
+
+#[test]
+fn security_scheme() {
+    use aidecomment::AideCommentSecurityScheme;
+    /// Bearer JWT issued by /login
+    #[derive(AideCommentSecurityScheme)]
+    #[scheme(bearer(format = "JWT"))]
+    struct BearerAuth;
+
+    /// A key for scripts, which is created in the settings
+    #[derive(AideCommentSecurityScheme)]
+    #[scheme(api_key(header = "X-API-Key"), name = "api_key")]
+    struct ScriptKey;
+
+    use aide::{
+        axum::ApiRouter,
+        openapi::{ReferenceOr, SecurityScheme},
+    };
+    let api = common::document_with(ApiRouter::<()>::new(), |api| {
+        ScriptKey::register(BearerAuth::register(api))
+    });
+    let schemes = api.components.unwrap().security_schemes;
+    let ReferenceOr::Item(SecurityScheme::Http {
+        bearer_format,
+        description,
+        ..
+    }) = &schemes["bearer_auth"]
+    else {
+        panic!()
+    };
+    assert_eq!(bearer_format.as_deref(), Some("JWT"));
+    assert_eq!(description.as_deref(), Some("Bearer JWT issued by /login"));
+    assert!(
+        matches!(&schemes["api_key"], ReferenceOr::Item(SecurityScheme::ApiKey { name, .. }) if name == "X-API-Key")
+    );
+}
+
+#[test]
+fn params() {
+    use aidecomment::AideCommentParams;
+    use serde::Deserialize;
+    #[derive(Deserialize, AideCommentParams)]
+    struct Pagination {
+        /// The page to get, starting at 1
+        page: u32,
+        /// How many items are on a page
+        #[serde(rename = "perPage")]
+        per_page: u32,
+    }
+    assert_eq!(
+        Pagination::AIDECOMMENT_PARAMETERS,
+        [
+            ("page", "The page to get, starting at 1"),
+            ("perPage", "How many items are on a page")
+        ]
+    );
+}
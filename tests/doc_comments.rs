@@ -0,0 +1,363 @@
+//! What the doc comments of handlers are understood as.
+
+// the types only exist for what is derived or documented from them
+#![allow(dead_code)]
+
+mod common;
+
+#[test]
+fn summary_and_description() {
+    use aidecomment::aidecomment;
+    /// This is a summary
+    ///
+    /// This is a longer description of the endpoint that is expected to be much
+    /// more detailed and may span more lines than the first paragraph summary.
+    #[aidecomment]
+    async fn my_handler() -> &'static str {
+        "hello world"
+    }
+    // it is still an axum handler
+    let _ = axum::Router::<()>::new().route("/", axum::routing::get(my_handler));
+    let operation = common::operation(aide::axum::routing::get(my_handler));
+    assert_eq!(operation.summary.as_deref(), Some("This is a summary"));
+    assert_eq!(
+        operation.description.as_deref(),
+        Some("This is a longer description of the endpoint that is expected to be much\n more detailed and may span more lines than the first paragraph summary."),
+    );
+}
+
+#[test]
+fn generic_handler() {
+    use aidecomment::aidecomment;
+    use axum::{extract::State, Json};
+
+    trait Store: Clone + Send + Sync + 'static {
+        fn users(&self) -> Vec<String>;
+    }
+
+    /// Lists users
+    ///
+    /// # Responses
+    ///
+    /// - 200: The names of the users
+    #[aidecomment]
+    async fn list_users<S>(State(store): State<S>) -> Json<Vec<String>>
+    where
+        S: Store,
+    {
+        Json(store.users())
+    }
+    #[derive(Clone)]
+    struct Memory;
+    impl Store for Memory {
+        fn users(&self) -> Vec<String> {
+            Vec::new()
+        }
+    }
+    use aide::axum::{routing::get, ApiRouter};
+    let api = common::document(ApiRouter::new().api_route("/", get(list_users::<Memory>)));
+    let operation = common::path_item(&api, "/").get.unwrap();
+    assert_eq!(operation.summary.as_deref(), Some("Lists users"));
+}
+
+#[test]
+fn evaluated_doc_attributes() {
+    use aidecomment::aidecomment;
+    #[doc = concat!("Gets the version\n\nThis is version ", env!("CARGO_PKG_VERSION"), ".")]
+    #[aidecomment]
+    async fn version() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(version));
+    assert_eq!(operation.summary.as_deref(), Some("Gets the version"));
+    assert_eq!(
+        operation.description,
+        Some(format!("This is version {}.", env!("CARGO_PKG_VERSION")))
+    );
+}
+
+#[test]
+fn file_argument() {
+    use aidecomment::aidecomment;
+    #[aidecomment(file = "../docs/get_user.md")]
+    async fn get_user() {}
+    use aide::{axum::routing::get, openapi::StatusCode};
+    let operation = common::operation(get(get_user));
+    assert_eq!(operation.summary.as_deref(), Some("Gets a user"));
+    assert_eq!(
+        operation.description.as_deref(),
+        Some("Looks up the user by their id.")
+    );
+    assert!(operation
+        .responses
+        .unwrap()
+        .responses
+        .contains_key(&StatusCode::Code(404)));
+}
+
+#[test]
+fn placeholders() {
+    use aidecomment::aidecomment;
+    /// Gets the status of the API
+    ///
+    /// This is version {crate_version} of {env:CARGO_PKG_NAME}, served at `/{id}`.
+    #[aidecomment]
+    async fn status() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(status));
+    assert_eq!(
+        operation.description,
+        Some(format!(
+            "This is version {} of aidecomment, served at `/{{id}}`.",
+            env!("CARGO_PKG_VERSION")
+        ))
+    );
+}
+
+#[test]
+fn escaped_placeholders() {
+    use aidecomment::aidecomment;
+    /// Gets the status of the API
+    ///
+    /// Mentions a version with {{crate_version}}.
+    ///
+    /// ```text
+    /// curl {env:AIDECOMMENT_UNSET_EXAMPLE}/status
+    /// ```
+    #[aidecomment]
+    async fn status() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(status));
+    assert_eq!(
+        operation.description.as_deref(),
+        Some(concat!(
+            "Mentions a version with {crate_version}.\n",
+            "\n",
+            " ```text\n",
+            " curl {env:AIDECOMMENT_UNSET_EXAMPLE}/status\n",
+            " ```",
+        ))
+    );
+}
+
+#[test]
+fn snippets() {
+    use aidecomment::aidecomment;
+    /// Deletes a user
+    ///
+    /// {{include:errors/envelope}}
+    ///
+    /// Note: {{include:auth_note}}
+    #[aidecomment]
+    async fn delete_user() {}
+    use aide::axum::routing::delete;
+    let operation = common::operation(delete(delete_user));
+    assert_eq!(
+        operation.description.as_deref(),
+        Some(concat!(
+            "Errors are returned in an envelope with a `code` and a `message`.\n",
+            "\n",
+            " Requests need a bearer token in the `Authorization` header.\n",
+            "\n",
+            " Note: Requests need a bearer token in the `Authorization` header.",
+        ))
+    );
+}
+
+#[test]
+fn languages() {
+    use aidecomment::aidecomment;
+    /// # Errors
+    ///
+    /// - 404: there is no such user
+    ///
+    /// # [en]
+    /// Gets a user
+    ///
+    /// Looks up the user by their id.
+    ///
+    /// # [de]
+    /// Holt einen Benutzer
+    ///
+    /// Sucht den Benutzer anhand seiner ID.
+    #[aidecomment]
+    async fn get_user() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(get_user));
+    assert_eq!(operation.summary.as_deref(), Some("Gets a user"));
+    assert_eq!(
+        operation.description.as_deref(),
+        Some("Looks up the user by their id.")
+    );
+    assert!(operation
+        .responses
+        .unwrap()
+        .responses
+        .contains_key(&aide::openapi::StatusCode::Code(404)));
+}
+
+#[test]
+fn badges() {
+    use aidecomment::aidecomment;
+    /// [BETA] Lists the recommended products
+    #[aidecomment]
+    async fn recommendations() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(recommendations));
+    assert_eq!(
+        operation.summary.as_deref(),
+        Some("Lists the recommended products")
+    );
+    assert_eq!(
+        operation.extensions["x-badges"],
+        serde_json::json!([{ "name": "Beta" }])
+    );
+}
+
+#[test]
+fn directives() {
+    use aidecomment::aidecomment;
+    /// Deletes a user
+    ///
+    /// @x-audit-level: high
+    /// @x-requires-mfa
+    #[aidecomment]
+    async fn delete_user() {}
+    use aide::axum::routing::delete;
+    let operation = common::operation(delete(delete_user));
+    assert_eq!(operation.extensions["x-audit-level"], "high");
+    assert_eq!(operation.extensions["x-requires-mfa"], true);
+    assert_eq!(operation.description.as_deref(), Some(""));
+}
+
+#[test]
+fn comment_directives() {
+    use aidecomment::aidecomment;
+    /// Deletes a user <!-- aide: tag=admin operation_id=deleteUser -->
+    ///
+    /// <!-- aide: x-audit-level=high
+    ///      deprecated="use the archive endpoint instead" -->
+    #[aidecomment]
+    async fn delete_user() {}
+    use aide::axum::routing::delete;
+    let operation = common::operation(delete(delete_user));
+    assert_eq!(operation.summary.as_deref(), Some("Deletes a user"));
+    assert_eq!(operation.tags, ["admin"]);
+    assert_eq!(operation.operation_id.as_deref(), Some("deleteUser"));
+    assert_eq!(operation.extensions["x-audit-level"], "high");
+    assert!(operation.deprecated);
+    assert_eq!(
+        operation.description.as_deref(),
+        Some("**Deprecated:** use the archive endpoint instead")
+    );
+}
+
+#[test]
+fn tags() {
+    use aidecomment::{aidecomment, documented};
+    use axum::{extract::Query, Json};
+    #[derive(serde::Deserialize, schemars::JsonSchema)]
+    struct Search {
+        name: String,
+    }
+    /// Searches users
+    ///
+    /// @param {string} name - part of the name of the user
+    /// @returns the users that were found
+    /// @throws 400 the name is empty
+    /// @tags users
+    #[aidecomment(transform_only, jsdoc)]
+    async fn search_users(Query(search): Query<Search>) -> Json<Vec<String>> {
+        Json(vec![search.name])
+    }
+
+    let operation = common::operation(documented!(get search_users));
+    let parameter = operation.parameters[0]
+        .as_item()
+        .unwrap()
+        .parameter_data_ref();
+    assert_eq!(
+        parameter.description.as_deref(),
+        Some("part of the name of the user")
+    );
+    assert_eq!(
+        common::response(&operation, 200).description,
+        "the users that were found"
+    );
+    assert_eq!(
+        common::response(&operation, 400).description,
+        "the name is empty"
+    );
+    assert_eq!(operation.tags, ["users"]);
+    assert_eq!(operation.description.as_deref(), Some(""));
+}
+
+#[test]
+fn tags_before_sections() {
+    use aidecomment::aidecomment;
+    use axum::extract::Query;
+    #[derive(serde::Deserialize, schemars::JsonSchema)]
+    struct Search {
+        name: String,
+    }
+    /// Searches users
+    ///
+    /// @param name - part of the name of the user
+    /// # Errors
+    /// - 404: there is no such user
+    #[aidecomment(jsdoc)]
+    async fn search_users(Query(_search): Query<Search>) {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(search_users));
+    let parameter = operation.parameters[0]
+        .as_item()
+        .unwrap()
+        .parameter_data_ref();
+    assert_eq!(
+        parameter.description.as_deref(),
+        Some("part of the name of the user")
+    );
+    assert_eq!(
+        common::response(&operation, 404).description,
+        "there is no such user"
+    );
+}
+
+#[test]
+fn tags_are_opt_in() {
+    use aidecomment::aidecomment;
+    /// Renders a template
+    ///
+    /// Each parameter of the template is written as
+    /// @param followed by its name.
+    #[aidecomment]
+    async fn render() {}
+    use aide::axum::routing::post;
+    let operation = common::operation(post(render));
+    assert_eq!(
+        operation.description.as_deref(),
+        Some("Each parameter of the template is written as\n @param followed by its name."),
+    );
+    assert!(operation.parameters.is_empty());
+}
+
+#[test]
+fn front_matter() {
+    use aidecomment::aidecomment;
+    /// ```yaml
+    /// operationId: getUser
+    /// tags: [users]
+    /// x-audience:
+    ///   - internal
+    ///   - partner
+    /// ```
+    ///
+    /// Gets a user
+    #[aidecomment]
+    async fn get_user() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(get_user));
+    assert_eq!(operation.operation_id.as_deref(), Some("getUser"));
+    assert_eq!(operation.tags, ["users"]);
+    assert_eq!(operation.extensions["x-audience"][1], "partner");
+    assert_eq!(operation.summary.as_deref(), Some("Gets a user"));
+}
@@ -0,0 +1,205 @@
+//! Documenting the handlers of impl blocks, traits, and modules.
+
+mod common;
+
+#[test]
+fn impl_block() {
+    use aide::axum::{routing::get, ApiRouter};
+    use aidecomment::aidecomment;
+    use axum::extract::State;
+
+    #[derive(Clone)]
+    struct App {
+        name: String,
+    }
+
+    #[aidecomment]
+    impl App {
+        /// Gets the name of the app
+        async fn name(State(app): State<Self>) -> String {
+            app.name
+        }
+
+        /// Gets the name of the app as plain text
+        #[aidecomment(inherit = Self::name)]
+        async fn name_text(State(app): State<Self>) -> String {
+            app.name
+        }
+
+        fn router() -> ApiRouter<Self> {
+            ApiRouter::new()
+                .api_route("/name", get(Self::name))
+                .api_route("/name.txt", get(Self::name_text))
+        }
+    }
+    let api = common::document(App::router());
+    let operation = common::path_item(&api, "/name.txt").get.unwrap();
+    assert_eq!(
+        operation.summary.as_deref(),
+        Some("Gets the name of the app")
+    );
+}
+
+#[test]
+fn impl_block_with_own_arguments() {
+    use aidecomment::aidecomment;
+    struct Users;
+
+    #[aidecomment(tag = "users")]
+    impl Users {
+        /// Lists users
+        async fn list() {}
+
+        /// Gets a user
+        #[aidecomment(tag = "admin")]
+        async fn get() {}
+    }
+    use aide::axum::{routing::get, ApiRouter};
+    let api = common::document(
+        ApiRouter::<()>::new()
+            .api_route("/", get(Users::list))
+            .api_route("/user", get(Users::get)),
+    );
+    let list = common::path_item(&api, "/").get.unwrap();
+    let get = common::path_item(&api, "/user").get.unwrap();
+    assert_eq!(list.summary.as_deref(), Some("Lists users"));
+    assert_eq!(list.tags, ["users"]);
+    assert_eq!(get.summary.as_deref(), Some("Gets a user"));
+    assert_eq!(get.tags, ["admin"]);
+}
+
+#[test]
+fn trait_and_implementations() {
+    use aidecomment::aidecomment;
+    use std::future::Future;
+    #[aidecomment(tag = "users")]
+    trait Users {
+        /// Lists users
+        fn list() -> impl Future<Output = ()> + Send;
+    }
+
+    struct Database;
+
+    #[aidecomment]
+    impl Users for Database {
+        /// Lists the users in the database
+        async fn list() {}
+    }
+    use aide::axum::routing::get;
+    let operation = common::operation(get(Database::list));
+    assert_eq!(
+        operation.summary.as_deref(),
+        Some("Lists the users in the database")
+    );
+    assert_eq!(operation.tags, ["users"]);
+}
+
+#[test]
+fn trait_arguments() {
+    use aidecomment::aidecomment;
+    use axum::extract::Path;
+    use std::future::Future;
+
+    #[aidecomment]
+    trait Users {
+        /// Gets a user
+        fn get(
+            #[aidecomment(doc = "the id of the user")] id: Path<u64>,
+        ) -> impl Future<Output = ()> + Send;
+    }
+
+    struct Database;
+
+    #[aidecomment]
+    impl Users for Database {
+        /// Gets a user from the database
+        async fn get(
+            #[aidecomment(doc = "the id of the user in the database")] Path(_id): Path<u64>,
+        ) {
+        }
+    }
+    use aide::axum::{routing::get, ApiRouter};
+    let api = common::document(ApiRouter::<()>::new().api_route("/:id", get(Database::get)));
+    let operation = common::path_item(&api, "/{id}").get.unwrap();
+    assert_eq!(
+        operation.summary.as_deref(),
+        Some("Gets a user from the database")
+    );
+    let aide::openapi::ReferenceOr::Item(parameter) = &operation.parameters[0] else {
+        panic!()
+    };
+    assert_eq!(parameter.parameter_data_ref().name, "id");
+    assert_eq!(
+        parameter.parameter_data_ref().description.as_deref(),
+        Some("the id of the user")
+    );
+}
+
+#[test]
+fn module() {
+    use aidecomment::aidecomment;
+    #[aidecomment(tag = "orders")]
+    mod orders {
+        /// Lists orders
+        pub async fn list() {}
+
+        /// Cancels an order
+        #[aidecomment(tag = "admin")]
+        pub async fn cancel() {}
+
+        // not a handler, so it is left alone
+        #[allow(dead_code)]
+        async fn load() {}
+    }
+    use aide::axum::{routing::get, ApiRouter};
+    let api = common::document(
+        ApiRouter::<()>::new().api_route("/", get(orders::list).delete(orders::cancel)),
+    );
+    let item = common::path_item(&api, "/");
+    assert_eq!(item.get.unwrap().tags, ["orders"]);
+    assert_eq!(item.delete.unwrap().tags, ["admin"]);
+}
+
+#[test]
+fn module_doc_comment_describes_tag() {
+    use aidecomment::aidecomment;
+    #[aidecomment(tag = "invoices")]
+    mod invoices {
+        //! Invoices are sent to customers at the end of every billing period.
+
+        /// Lists invoices
+        pub async fn list() {}
+    }
+    use aide::axum::{routing::get, ApiRouter};
+    let router = ApiRouter::<()>::new().api_route("/", get(invoices::list));
+    let api = common::document_with(router, invoices::AideCommentTag::register);
+    assert_eq!(api.tags[0].name, "invoices");
+    assert_eq!(
+        api.tags[0].description.as_deref(),
+        Some("Invoices are sent to customers at the end of every billing period.")
+    );
+}
+
+#[test]
+fn macro_rules_handlers() {
+    use aidecomment::aidecomment;
+    macro_rules! get_handler {
+        ($name:ident, $doc:expr, $tag:expr) => {
+            #[doc = $doc]
+            #[aidecomment(tag = $tag)]
+            async fn $name() {}
+        };
+    }
+
+    get_handler!(get_user, "Gets a user", "users");
+    get_handler!(get_order, "Gets an order", "orders");
+    use aide::axum::{routing::get, ApiRouter};
+    let api = common::document(
+        ApiRouter::<()>::new()
+            .api_route("/user", get(get_user))
+            .api_route("/order", get(get_order)),
+    );
+    let operation = common::path_item(&api, "/order").get.unwrap();
+    assert_eq!(operation.summary.as_deref(), Some("Gets an order"));
+    assert_eq!(operation.tags, ["orders"]);
+}
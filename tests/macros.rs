@@ -0,0 +1,166 @@
+//! The function-like macros.
+
+mod common;
+
+#[test]
+fn middleware() {
+    use aide::axum::{routing::get, ApiRouter};
+    use aidecomment::{aidecomment, aidecomment_middleware};
+    use axum::{
+        extract::Request,
+        middleware::{self, Next},
+        response::Response,
+    };
+
+    /// Requires the `X-Tenant` header, which says which tenant the request is for.
+    #[aidecomment_middleware]
+    async fn require_tenant(request: Request, next: Next) -> Response {
+        next.run(request).await
+    }
+
+    /// Lists users
+    #[aidecomment]
+    async fn list_users() {}
+
+    let router = ApiRouter::<()>::new()
+        .api_route("/", get(list_users))
+        .with_path_items(require_tenant_AideComment::path_item)
+        .layer(middleware::from_fn(require_tenant));
+    let api = common::document(router);
+    let operation = common::path_item(&api, "/").get.unwrap();
+    assert_eq!(
+        operation.description.as_deref(),
+        Some("Requires the `X-Tenant` header, which says which tenant the request is for.")
+    );
+}
+
+#[test]
+fn closure() {
+    use aide::axum::{routing::get, ApiRouter};
+    use aidecomment::aidecomment_closure;
+    use axum::extract::Path;
+
+    let router = ApiRouter::<()>::new().api_route(
+        "/:id",
+        get(aidecomment_closure! {
+            /// Gets a user
+            #[aidecomment(tag = "users")]
+            |Path(id): Path<u64>| async move { id.to_string() }
+        }),
+    );
+    let api = common::document(router);
+    let operation = common::path_item(&api, "/{id}").get.unwrap();
+    assert_eq!(operation.summary.as_deref(), Some("Gets a user"));
+    assert_eq!(operation.tags, ["users"]);
+}
+
+#[test]
+fn routes() {
+    use aidecomment::aidecomment_routes;
+    use axum::extract::Path;
+
+    async fn get_user(Path(id): Path<u64>) -> String {
+        id.to_string()
+    }
+
+    async fn delete_user(Path(_id): Path<u64>) {}
+
+    let router: aide::axum::ApiRouter = aidecomment_routes! {
+        /// Gets a user
+        ///
+        /// The user is looked up by their id.
+        #[aidecomment(tag = "users")]
+        GET "/users/{id}" => get_user,
+        /// Deletes a user
+        DELETE "/users/{id}" => delete_user,
+    };
+    let api = common::document(router);
+    let item = common::path_item(&api, "/users/{id}");
+    let get = item.get.unwrap();
+    assert_eq!(get.summary.as_deref(), Some("Gets a user"));
+    assert_eq!(
+        get.description.as_deref(),
+        Some("The user is looked up by their id.")
+    );
+    assert_eq!(get.tags, ["users"]);
+    assert_eq!(
+        item.delete.unwrap().summary.as_deref(),
+        Some("Deletes a user")
+    );
+}
+
+#[test]
+fn documented() {
+    use aide::axum::ApiRouter;
+    use aidecomment::{aidecomment, documented};
+
+    /// Gets a user
+    #[aidecomment(transform_only)]
+    async fn get_user() {}
+
+    /// Deletes a user
+    #[aidecomment(transform_only, tag = "admin")]
+    async fn delete_user() {}
+
+    let router =
+        ApiRouter::<()>::new().api_route("/", documented!(get get_user, delete delete_user));
+    let api = common::document(router);
+    let item = common::path_item(&api, "/");
+    assert_eq!(item.get.unwrap().summary.as_deref(), Some("Gets a user"));
+    let delete = item.delete.unwrap();
+    assert_eq!(delete.summary.as_deref(), Some("Deletes a user"));
+    assert_eq!(delete.tags, ["admin"]);
+    let router =
+        ApiRouter::<()>::new().api_route("/", documented!(GET get_user, Delete delete_user));
+    let api = common::document(router);
+    let item = common::path_item(&api, "/");
+    assert_eq!(item.get.unwrap().summary.as_deref(), Some("Gets a user"));
+    assert_eq!(
+        item.delete.unwrap().summary.as_deref(),
+        Some("Deletes a user")
+    );
+}
+
+#[test]
+fn tag() {
+    use aidecomment::{aidecomment, aidecomment_tag};
+    aidecomment_tag! {
+        /// Everything about the users of the service
+        pub struct Users;
+
+        /// Operations for administrators
+        #[aidecomment(name = "admin", external_docs(url = "https://example.com/admin"))]
+        pub struct Administration;
+    }
+
+    /// Gets a user
+    #[aidecomment(tag = "users")]
+    async fn get_user() {}
+
+    use aide::axum::{routing::get, ApiRouter};
+    let router = ApiRouter::<()>::new().api_route("/", get(get_user));
+    let api = common::document_with(router, |api| Administration::register(Users::register(api)));
+    assert_eq!(Users::NAME, "users");
+    assert_eq!(
+        api.tags[0].description.as_deref(),
+        Some("Everything about the users of the service")
+    );
+    assert_eq!(api.tags[1].name, "admin");
+    assert_eq!(
+        api.tags[1].external_docs.as_ref().unwrap().url,
+        "https://example.com/admin"
+    );
+}
+
+#[test]
+fn info() {
+    use aide::{axum::ApiRouter, openapi::OpenApi};
+    use aidecomment::aidecomment_info;
+    let mut api = OpenApi {
+        info: aidecomment_info!(),
+        ..OpenApi::default()
+    };
+    let _ = ApiRouter::<()>::new().finish_api(&mut api);
+    assert_eq!(api.info.version, env!("CARGO_PKG_VERSION"));
+    assert!(api.info.title.starts_with("This crate provides a macro"));
+}
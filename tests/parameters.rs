@@ -0,0 +1,300 @@
+//! The sections that document the parameters.
+
+mod common;
+
+#[test]
+fn parameters() {
+    use aidecomment::aidecomment;
+    use axum::extract::{Path, Query};
+    #[derive(serde::Deserialize, schemars::JsonSchema)]
+    struct UserPath {
+        id: u64,
+    }
+    #[derive(serde::Deserialize, schemars::JsonSchema)]
+    struct Fields {
+        fields: String,
+    }
+    /// Gets a user
+    ///
+    /// # Parameters
+    ///
+    /// - `id`: the user identifier
+    /// - `fields`: which fields to include
+    #[aidecomment]
+    async fn get_user(Path(path): Path<UserPath>, Query(query): Query<Fields>) -> String {
+        format!("{} {}", path.id, query.fields)
+    }
+    use aide::axum::{routing::get, ApiRouter};
+    let api = common::document(ApiRouter::<()>::new().api_route("/:id", get(get_user)));
+    let operation = common::path_item(&api, "/{id}").get.unwrap();
+    let descriptions = operation
+        .parameters
+        .iter()
+        .map(|p| {
+            p.as_item()
+                .unwrap()
+                .parameter_data_ref()
+                .description
+                .as_deref()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        descriptions,
+        [Some("the user identifier"), Some("which fields to include")]
+    );
+}
+
+#[test]
+fn allowed_values() {
+    use aidecomment::aidecomment;
+    use axum::extract::Query;
+    #[derive(serde::Deserialize, schemars::JsonSchema)]
+    struct Sort {
+        sort: String,
+    }
+    /// Lists users
+    ///
+    /// # Parameters
+    ///
+    /// - `sort`: one of `asc`, `desc`
+    #[aidecomment]
+    async fn list_users(Query(sort): Query<Sort>) -> String {
+        sort.sort
+    }
+    use aide::{axum::routing::get, openapi::ParameterSchemaOrContent};
+    let operation = common::operation(get(list_users));
+    let parameter = operation.parameters[0]
+        .as_item()
+        .unwrap()
+        .parameter_data_ref();
+    let ParameterSchemaOrContent::Schema(schema) = &parameter.format else {
+        panic!()
+    };
+    let schema = schema.json_schema.clone().into_object();
+    assert_eq!(schema.enum_values, Some(vec!["asc".into(), "desc".into()]));
+    assert_eq!(
+        parameter.description.as_deref(),
+        Some("one of `asc`, `desc`")
+    );
+    /// Lists users by their name
+    ///
+    /// # Query
+    ///
+    /// - `sort`: İİ sorts by one of `é`
+    #[aidecomment]
+    async fn list_names(Query(_sort): Query<Sort>) {}
+    let operation = common::operation(get(list_names));
+    let parameter = operation.parameters[0]
+        .as_item()
+        .unwrap()
+        .parameter_data_ref();
+    let ParameterSchemaOrContent::Schema(schema) = &parameter.format else {
+        panic!()
+    };
+    assert_eq!(
+        schema.json_schema.clone().into_object().enum_values,
+        Some(vec!["é".into()])
+    );
+}
+
+#[test]
+fn constraints() {
+    use aidecomment::aidecomment;
+    use axum::extract::Query;
+    #[derive(serde::Deserialize, schemars::JsonSchema)]
+    struct Pagination {
+        limit: u32,
+    }
+    /// Lists users
+    ///
+    /// # Parameters
+    ///
+    /// - `limit`: page size (min 1, max 100)
+    #[aidecomment]
+    async fn list_users(Query(pagination): Query<Pagination>) -> String {
+        pagination.limit.to_string()
+    }
+    use aide::{axum::routing::get, openapi::ParameterSchemaOrContent};
+    let operation = common::operation(get(list_users));
+    let parameter = operation.parameters[0]
+        .as_item()
+        .unwrap()
+        .parameter_data_ref();
+    let ParameterSchemaOrContent::Schema(schema) = &parameter.format else {
+        panic!()
+    };
+    let mut schema = schema.json_schema.clone().into_object();
+    assert_eq!(schema.number().minimum, Some(1.0));
+    assert_eq!(schema.number().maximum, Some(100.0));
+}
+
+#[test]
+fn parameter_example() {
+    use aidecomment::aidecomment;
+    use axum::extract::Path;
+    #[derive(serde::Deserialize, schemars::JsonSchema)]
+    struct UserPath {
+        id: u64,
+    }
+    /// Gets a user
+    ///
+    /// # Parameters
+    ///
+    /// - `id`: the user identifier (e.g. 42)
+    #[aidecomment]
+    async fn get_user(Path(path): Path<UserPath>) -> String {
+        path.id.to_string()
+    }
+    use aide::axum::{routing::get, ApiRouter};
+    let api = common::document(ApiRouter::<()>::new().api_route("/:id", get(get_user)));
+    let operation = common::path_item(&api, "/{id}").get.unwrap();
+    let parameter = operation.parameters[0]
+        .as_item()
+        .unwrap()
+        .parameter_data_ref();
+    assert_eq!(parameter.example, Some(42.into()));
+    assert_eq!(
+        parameter.description.as_deref(),
+        Some("the user identifier")
+    );
+}
+
+#[test]
+fn query() {
+    use aidecomment::aidecomment;
+    use axum::extract::Query;
+    #[derive(serde::Deserialize, schemars::JsonSchema)]
+    struct Pagination {
+        page: u32,
+    }
+    /// Lists users
+    ///
+    /// # Query
+    ///
+    /// - `page`: the page to get, starting at 1
+    #[aidecomment]
+    async fn list_users(Query(pagination): Query<Pagination>) -> String {
+        pagination.page.to_string()
+    }
+    use aide::{axum::routing::get, openapi::Parameter};
+    let operation = common::operation(get(list_users));
+    let Parameter::Query { parameter_data, .. } = operation.parameters[0].as_item().unwrap() else {
+        panic!()
+    };
+    assert_eq!(
+        parameter_data.description.as_deref(),
+        Some("the page to get, starting at 1")
+    );
+}
+
+#[test]
+fn path() {
+    use aidecomment::aidecomment;
+    use axum::extract::Path;
+    #[derive(serde::Deserialize, schemars::JsonSchema)]
+    struct OrgPath {
+        org_id: u64,
+    }
+    /// Gets an organization
+    ///
+    /// # Path
+    ///
+    /// - `org_id`: owning organization
+    #[aidecomment]
+    async fn get_org(Path(path): Path<OrgPath>) -> String {
+        path.org_id.to_string()
+    }
+    use aide::{
+        axum::{routing::get, ApiRouter},
+        openapi::Parameter,
+    };
+    let api = common::document(ApiRouter::<()>::new().api_route("/:org_id", get(get_org)));
+    let operation = common::path_item(&api, "/{org_id}").get.unwrap();
+    let Parameter::Path { parameter_data, .. } = operation.parameters[0].as_item().unwrap() else {
+        panic!()
+    };
+    assert_eq!(
+        parameter_data.description.as_deref(),
+        Some("owning organization")
+    );
+}
+
+#[test]
+fn path_argument_doc() {
+    use aidecomment::aidecomment;
+    use axum::extract::Path;
+    /// Gets a tenant
+    #[aidecomment]
+    async fn get_tenant(
+        #[aidecomment(doc = "the tenant identifier")] Path(tenant): Path<String>,
+    ) -> String {
+        tenant
+    }
+    use aide::{
+        axum::{routing::get, ApiRouter},
+        openapi::Parameter,
+    };
+    let api = common::document(ApiRouter::<()>::new().api_route("/:tenant", get(get_tenant)));
+    let operation = common::path_item(&api, "/{tenant}").get.unwrap();
+    let Parameter::Path { parameter_data, .. } = operation.parameters[0].as_item().unwrap() else {
+        panic!()
+    };
+    assert_eq!(parameter_data.name, "tenant");
+    assert_eq!(
+        parameter_data.description.as_deref(),
+        Some("the tenant identifier")
+    );
+    assert!(parameter_data.required);
+}
+
+#[test]
+fn headers() {
+    use aidecomment::aidecomment;
+    /// Gets a user
+    ///
+    /// # Headers
+    ///
+    /// - `X-Request-Id`: correlation id, required
+    #[aidecomment]
+    async fn get_user() -> String {
+        "alice".to_owned()
+    }
+    use aide::{axum::routing::get, openapi::Parameter};
+    let operation = common::operation(get(get_user));
+    let Parameter::Header { parameter_data, .. } = operation.parameters[0].as_item().unwrap()
+    else {
+        panic!()
+    };
+    assert_eq!(parameter_data.name, "X-Request-Id");
+    assert_eq!(
+        parameter_data.description.as_deref(),
+        Some("correlation id")
+    );
+    assert!(parameter_data.required);
+}
+
+#[test]
+fn cookies() {
+    use aidecomment::aidecomment;
+    /// Gets the current user
+    ///
+    /// # Cookies
+    ///
+    /// - `session`: the session token, required
+    #[aidecomment]
+    async fn get_me() -> String {
+        "alice".to_owned()
+    }
+    use aide::{axum::routing::get, openapi::Parameter};
+    let operation = common::operation(get(get_me));
+    let Parameter::Cookie { parameter_data, .. } = operation.parameters[0].as_item().unwrap()
+    else {
+        panic!()
+    };
+    assert_eq!(parameter_data.name, "session");
+    assert_eq!(
+        parameter_data.description.as_deref(),
+        Some("the session token")
+    );
+    assert!(parameter_data.required);
+}
@@ -0,0 +1,424 @@
+//! The sections that document the responses and request body.
+
+mod common;
+
+#[test]
+fn errors() {
+    use aidecomment::aidecomment;
+    /// Gets a user
+    ///
+    /// # Errors
+    ///
+    /// - 404: user not found
+    /// - 5XX: the database is unavailable
+    #[aidecomment]
+    async fn get_user() -> &'static str {
+        "hello world"
+    }
+    use aide::{axum::routing::get, openapi::StatusCode};
+    aide::gen::on_error(|error| panic!("{error}"));
+    let operation = common::operation(get(get_user));
+    let responses = operation.responses.unwrap().responses;
+    assert!(responses[&StatusCode::Code(200)]
+        .as_item()
+        .unwrap()
+        .content
+        .contains_key("text/plain; charset=utf-8"));
+    assert_eq!(
+        responses[&StatusCode::Code(404)]
+            .as_item()
+            .unwrap()
+            .description,
+        "user not found"
+    );
+    assert_eq!(
+        responses[&StatusCode::Range(5)]
+            .as_item()
+            .unwrap()
+            .description,
+        "the database is unavailable"
+    );
+    assert_eq!(operation.description.as_deref(), Some(""));
+}
+
+#[test]
+fn responses() {
+    use aidecomment::{aidecomment, documented};
+    use axum::Json;
+    /// Creates a user
+    ///
+    /// # Responses
+    ///
+    /// - 200: the user that was created
+    /// - 409: a user with that name already exists
+    #[aidecomment(transform_only)]
+    async fn create_user() -> Json<String> {
+        Json("hello world".to_owned())
+    }
+    use aide::openapi::StatusCode;
+    aide::gen::on_error(|error| panic!("{error}"));
+    let operation = common::operation(documented!(post create_user));
+    let responses = operation.responses.unwrap().responses;
+    let ok = responses[&StatusCode::Code(200)].as_item().unwrap();
+    assert_eq!(ok.description, "the user that was created");
+    assert!(ok.content.contains_key("application/json"));
+    assert_eq!(
+        responses[&StatusCode::Code(409)]
+            .as_item()
+            .unwrap()
+            .description,
+        "a user with that name already exists"
+    );
+}
+
+#[test]
+fn returns() {
+    use aidecomment::{aidecomment, documented};
+    use axum::Json;
+    /// Lists users
+    ///
+    /// # Returns
+    ///
+    /// The names of all users, sorted alphabetically.
+    #[aidecomment(transform_only)]
+    async fn list_users() -> Json<Vec<String>> {
+        Json(Vec::new())
+    }
+
+    let operation = common::operation(documented!(get list_users));
+    let ok = common::response(&operation, 200);
+    assert_eq!(
+        ok.description,
+        "The names of all users, sorted alphabetically."
+    );
+    assert!(ok.content.contains_key("application/json"));
+    assert_eq!(operation.description.as_deref(), Some(""));
+}
+
+#[test]
+fn returns_without_transform() {
+    use aidecomment::aidecomment;
+    use axum::{
+        response::{IntoResponse, Response},
+        Json,
+    };
+    /// Lists users
+    ///
+    /// # Returns
+    ///
+    /// The names of all users, sorted alphabetically.
+    #[aidecomment]
+    async fn list_users() -> Json<Vec<String>> {
+        Json(Vec::new())
+    }
+
+    /// Exports the users
+    ///
+    /// # Returns
+    ///
+    /// A CSV file of the users.
+    #[aidecomment]
+    async fn export_users() -> Response {
+        "alice".into_response()
+    }
+    use aide::axum::{routing::get, ApiRouter};
+    aide::gen::on_error(|error| panic!("{error}"));
+    let api = common::document(
+        ApiRouter::<()>::new()
+            .api_route("/", get(list_users))
+            .api_route("/export", get(export_users)),
+    );
+    let operation = common::path_item(&api, "/").get.unwrap();
+    assert!(common::response(&operation, 200)
+        .content
+        .contains_key("application/json"));
+    let operation = common::path_item(&api, "/export").get.unwrap();
+    assert_eq!(
+        common::response(&operation, 200).description,
+        "A CSV file of the users."
+    );
+}
+
+#[test]
+fn response_headers() {
+    use aidecomment::{aidecomment, documented};
+    use axum::Json;
+    /// Creates a user
+    ///
+    /// # Response Headers
+    ///
+    /// - `ETag`: the version of the user
+    ///
+    /// ## 429
+    ///
+    /// - `Retry-After`: how many seconds to wait (e.g. 30)
+    #[aidecomment(transform_only)]
+    async fn create_user() -> Json<String> {
+        Json("alice".to_owned())
+    }
+
+    let operation = common::operation(documented!(post create_user));
+    let ok = common::response(&operation, 200);
+    assert!(ok.content.contains_key("application/json"));
+    let etag = ok.headers["ETag"].as_item().unwrap();
+    assert_eq!(etag.description.as_deref(), Some("the version of the user"));
+    let too_many = common::response(&operation, 429);
+    assert_eq!(
+        too_many.headers["Retry-After"].as_item().unwrap().example,
+        Some(30.into())
+    );
+    assert_eq!(operation.description.as_deref(), Some(""));
+}
+
+#[test]
+fn request_body() {
+    use aidecomment::aidecomment;
+    use axum::Json;
+    /// Renames a user
+    ///
+    /// # Request Body
+    ///
+    /// The new name of the user.
+    #[aidecomment]
+    async fn rename_user(Json(name): Json<String>) -> String {
+        name
+    }
+    use aide::axum::routing::put;
+    let operation = common::operation(put(rename_user));
+    let request_body = operation.request_body.as_ref().unwrap().as_item().unwrap();
+    assert_eq!(
+        request_body.description.as_deref(),
+        Some("The new name of the user.")
+    );
+    assert!(request_body.content.contains_key("application/json"));
+}
+
+#[test]
+fn request_body_example() {
+    use aidecomment::aidecomment;
+    use axum::Json;
+    /// Renames a user
+    ///
+    /// # Body
+    ///
+    /// ```json
+    /// "alice"
+    /// ```
+    #[aidecomment]
+    async fn rename_user(Json(name): Json<String>) -> String {
+        name
+    }
+    use aide::axum::routing::put;
+    let operation = common::operation(put(rename_user));
+    let request_body = operation.request_body.as_ref().unwrap().as_item().unwrap();
+    assert_eq!(
+        request_body.content["application/json"]
+            .example
+            .as_ref()
+            .unwrap(),
+        "alice"
+    );
+    assert!(request_body.content["application/json"].schema.is_some());
+    assert_eq!(request_body.description, None);
+}
+
+#[test]
+fn upload() {
+    use aidecomment::aidecomment;
+    /// Uploads an avatar
+    ///
+    /// # Upload
+    ///
+    /// - `image` (binary, required): The image, at most 512x512 pixels
+    /// - `caption`: Shown when the image is hovered over
+    #[aidecomment]
+    async fn upload_avatar() {}
+    use aide::axum::routing::post;
+    let operation = common::operation(post(upload_avatar));
+    let request_body = operation.request_body.as_ref().unwrap().as_item().unwrap();
+    let schema = request_body.content["multipart/form-data"]
+        .schema
+        .clone()
+        .unwrap()
+        .json_schema
+        .into_object();
+    let object = schema.object.unwrap();
+    assert!(object.required.contains("image"));
+    let image = object.properties["image"].clone().into_object();
+    assert_eq!(image.format.as_deref(), Some("binary"));
+    assert_eq!(
+        image.metadata.unwrap().description.as_deref(),
+        Some("The image, at most 512x512 pixels")
+    );
+    let caption = object.properties["caption"].clone().into_object();
+    assert_eq!(
+        caption.metadata.unwrap().description.as_deref(),
+        Some("Shown when the image is hovered over")
+    );
+    assert!(!object.required.contains("caption"));
+    assert_eq!(
+        schema.instance_type,
+        Some(schemars::schema::InstanceType::Object.into())
+    );
+}
+
+#[test]
+fn examples() {
+    use aidecomment::{aidecomment, documented};
+    use axum::Json;
+    /// Creates a user
+    ///
+    /// # Examples
+    ///
+    /// Request:
+    ///
+    /// ```json
+    /// "alice"
+    /// ```
+    ///
+    /// Response:
+    ///
+    /// ```json
+    /// { "id": 1, "name": "alice" }
+    /// ```
+    #[aidecomment(transform_only)]
+    async fn create_user(Json(name): Json<String>) -> Json<String> {
+        Json(name)
+    }
+
+    let operation = common::operation(documented!(post create_user));
+    let request_body = operation.request_body.as_ref().unwrap().as_item().unwrap();
+    let example = request_body.content["application/json"]
+        .example
+        .as_ref()
+        .unwrap();
+    assert_eq!(example, "alice");
+    let ok = common::response(&operation, 200);
+    let example = ok.content["application/json"].example.as_ref().unwrap();
+    assert_eq!(example["name"], "alice");
+    assert!(ok.content["application/json"].schema.is_some());
+    assert_eq!(operation.description.as_deref(), Some(""));
+}
+
+#[test]
+fn examples_of_other_media_types() {
+    use aidecomment::{aidecomment, documented};
+    /// Gets a feed
+    ///
+    /// # Examples
+    ///
+    /// ```xml
+    /// <feed></feed>
+    /// ```
+    #[aidecomment(transform_only)]
+    async fn get_feed() -> String {
+        "<feed></feed>".to_owned()
+    }
+
+    let operation = common::operation(documented!(get get_feed));
+    let ok = common::response(&operation, 200);
+    assert_eq!(
+        ok.content["application/xml"].example.as_ref().unwrap(),
+        "<feed></feed>"
+    );
+}
+
+#[test]
+fn named_examples() {
+    use aidecomment::aidecomment;
+    use axum::Json;
+    /// Creates a user
+    ///
+    /// # Examples
+    ///
+    /// ## Minimal request
+    ///
+    /// ```json
+    /// { "name": "alice" }
+    /// ```
+    ///
+    /// ## Full request
+    ///
+    /// ```json
+    /// { "name": "alice", "email": "alice@example.com" }
+    /// ```
+    #[aidecomment]
+    async fn create_user(Json(user): Json<String>) -> Json<String> {
+        Json(user)
+    }
+    use aide::axum::routing::post;
+    let operation = common::operation(post(create_user));
+    let request_body = operation.request_body.as_ref().unwrap().as_item().unwrap();
+    let examples = &request_body.content["application/json"].examples;
+    assert_eq!(
+        examples.keys().collect::<Vec<_>>(),
+        ["Minimal request", "Full request"]
+    );
+    assert_eq!(
+        examples["Full request"]
+            .as_item()
+            .unwrap()
+            .value
+            .as_ref()
+            .unwrap()["email"],
+        "alice@example.com"
+    );
+    assert_eq!(operation.description.as_deref(), Some(""));
+}
+
+#[test]
+fn examples_of_statuses() {
+    use aidecomment::{aidecomment, documented};
+    use axum::Json;
+    /// Gets a user
+    ///
+    /// # Examples
+    ///
+    /// ### 200
+    ///
+    /// ```json
+    /// { "id": 1, "name": "alice" }
+    /// ```
+    ///
+    /// ### 404
+    ///
+    /// ```json
+    /// { "error": "user not found" }
+    /// ```
+    #[aidecomment(transform_only)]
+    async fn get_user() -> Json<String> {
+        Json("alice".to_owned())
+    }
+
+    let operation = common::operation(documented!(get get_user));
+    let ok = common::response(&operation, 200).content["application/json"].clone();
+    assert_eq!(ok.example.as_ref().unwrap()["name"], "alice");
+    assert!(ok.schema.is_some());
+    let not_found = common::response(&operation, 404).content["application/json"].clone();
+    assert_eq!(
+        not_found.example.as_ref().unwrap()["error"],
+        "user not found"
+    );
+    assert_eq!(operation.description.as_deref(), Some(""));
+}
+
+#[test]
+fn code_samples() {
+    use aidecomment::aidecomment;
+    /// Gets a user
+    ///
+    /// # Examples
+    ///
+    /// ```curl
+    /// curl https://example.com/users/1
+    /// ```
+    #[aidecomment]
+    async fn get_user() -> String {
+        "alice".to_owned()
+    }
+    use aide::axum::routing::get;
+    let operation = common::operation(get(get_user));
+    let samples = &operation.extensions["x-codeSamples"];
+    assert_eq!(samples[0]["label"], "curl");
+    assert_eq!(samples[0]["source"], "curl https://example.com/users/1");
+}
@@ -0,0 +1,458 @@
+//! The other sections of doc comments.
+
+mod common;
+
+#[test]
+fn protocol() {
+    use aidecomment::aidecomment;
+    /// Subscribes to chat messages
+    ///
+    /// # Protocol: websocket
+    ///
+    /// Each message is a JSON object.
+    #[aidecomment]
+    async fn chat() {}
+    use aide::{axum::routing::get, openapi::StatusCode};
+    let operation = common::operation(get(chat));
+    assert_eq!(
+        operation.summary.as_deref(),
+        Some("Subscribes to chat messages (WebSocket)")
+    );
+    assert_eq!(operation.extensions["x-protocol"], "websocket");
+    assert!(operation.description.as_deref().unwrap().starts_with(
+        "# Protocol: websocket\n\n Each message is a JSON object.\n\nThis endpoint upgrades"
+    ));
+    let responses = operation.responses.unwrap();
+    assert!(responses.responses.contains_key(&StatusCode::Code(101)));
+}
+
+#[test]
+fn limits() {
+    use aidecomment::aidecomment;
+    use axum::body::Bytes;
+    /// Uploads a file
+    ///
+    /// # Limits
+    ///
+    /// - Request body: 10 MB
+    #[aidecomment]
+    async fn upload(_body: Bytes) {}
+    use aide::axum::routing::post;
+    let operation = common::operation(post(upload));
+    assert_eq!(operation.extensions["x-max-request-size"], 10_000_000);
+    let request_body = operation.request_body.as_ref().unwrap().as_item().unwrap();
+    assert_eq!(
+        request_body.description.as_deref(),
+        Some("The request body can be at most 10 MB.")
+    );
+}
+
+#[test]
+fn compliance() {
+    use aidecomment::aidecomment;
+    /// Gets the profile of a user
+    ///
+    /// # Compliance
+    ///
+    /// - Classification: confidential
+    /// - PII: email, name, address
+    #[aidecomment]
+    async fn get_profile() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(get_profile));
+    assert_eq!(
+        operation.extensions["x-data-classification"],
+        "confidential"
+    );
+    assert_eq!(
+        operation.extensions["x-pii"],
+        serde_json::json!(["email", "name", "address"])
+    );
+    assert_eq!(operation.description.as_deref(), Some(""));
+}
+
+#[test]
+fn sla() {
+    use aidecomment::aidecomment;
+    /// Gets a user
+    ///
+    /// # SLA
+    ///
+    /// - p50 < 50ms
+    /// - p99 < 0.2s
+    /// - availability: 99.9%
+    #[aidecomment]
+    async fn get_user() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(get_user));
+    let sla = &operation.extensions["x-sla"];
+    assert_eq!(sla["latencyMs"]["p50"], 50.0);
+    assert_eq!(sla["latencyMs"]["p99"], 200.0);
+    assert_eq!(sla["availability"], 99.9);
+    assert_eq!(operation.description.as_deref(), Some(""));
+}
+
+#[test]
+fn cors() {
+    use aidecomment::aidecomment;
+    /// Gets the session
+    ///
+    /// # CORS
+    ///
+    /// Only the web app can read the session.
+    ///
+    /// - Origins: https://app.example.com
+    /// - Methods: GET, DELETE
+    /// - Credentials: yes
+    #[aidecomment]
+    async fn get_session() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(get_session));
+    let cors = &operation.extensions["x-cors"];
+    assert_eq!(cors["allowedOrigins"][0], "https://app.example.com");
+    assert_eq!(cors["allowedMethods"][1], "DELETE");
+    assert_eq!(cors["allowCredentials"], true);
+    assert_eq!(
+        operation.description.as_deref(),
+        Some("# CORS\n\n Only the web app can read the session.")
+    );
+}
+
+#[test]
+fn caching() {
+    use aidecomment::{aidecomment, documented};
+    use axum::Json;
+    /// Gets a user
+    ///
+    /// # Caching
+    ///
+    /// - `Cache-Control`: private, max-age=60
+    /// - `ETag`: changes whenever the user is updated
+    #[aidecomment(transform_only)]
+    async fn get_user() -> Json<String> {
+        Json("alice".to_owned())
+    }
+
+    let operation = common::operation(documented!(get get_user));
+    assert_eq!(operation.extensions["x-cacheable"], true);
+    let ok = common::response(&operation, 200);
+    let cache_control = ok.headers["Cache-Control"].as_item().unwrap();
+    assert_eq!(
+        cache_control.example.as_ref().unwrap(),
+        "private, max-age=60"
+    );
+    assert!(ok.headers.contains_key("ETag"));
+    assert_eq!(operation.description.as_deref(), Some(""));
+}
+
+#[test]
+fn idempotency() {
+    use aidecomment::aidecomment;
+    /// Creates an order
+    ///
+    /// # Idempotency
+    ///
+    /// Yes. When the same `Idempotency-Key` header is sent, the order is only
+    /// created once.
+    #[aidecomment]
+    async fn create_order() {}
+    use aide::axum::routing::post;
+    let operation = common::operation(post(create_order));
+    assert_eq!(operation.extensions["x-idempotent"], true);
+    assert_eq!(
+        operation.description.as_deref(),
+        Some("# Idempotency\n\nThis operation is idempotent. When the same `Idempotency-Key` header is sent, the order is only created once."),
+    );
+}
+
+#[test]
+fn pagination() {
+    use aidecomment::{aidecomment, documented};
+    use axum::Json;
+    /// Lists users
+    ///
+    /// # Pagination
+    ///
+    /// Users are sorted by name.
+    #[aidecomment(transform_only)]
+    async fn list_users() -> Json<Vec<String>> {
+        Json(Vec::new())
+    }
+
+    let operation = common::operation(documented!(get list_users));
+    let names = operation
+        .parameters
+        .iter()
+        .map(|p| p.as_item().unwrap().parameter_data_ref().name.as_str());
+    assert_eq!(names.collect::<Vec<_>>(), ["page", "per_page"]);
+    let ok = common::response(&operation, 200);
+    assert!(ok.description.starts_with("The results are paginated"));
+    assert!(ok.description.ends_with("Users are sorted by name."));
+    assert_eq!(operation.description.as_deref(), Some(""));
+}
+
+#[test]
+fn security() {
+    use aidecomment::aidecomment;
+    /// Deletes a user
+    ///
+    /// # Security
+    ///
+    /// - bearer_auth
+    /// - api_key + client_cert
+    #[aidecomment]
+    async fn delete_user() {}
+    use aide::axum::routing::delete;
+    let operation = common::operation(delete(delete_user));
+    assert_eq!(operation.security.len(), 2);
+    assert!(operation.security[0].contains_key("bearer_auth"));
+    assert_eq!(
+        operation.security[1].keys().collect::<Vec<_>>(),
+        ["api_key", "client_cert"]
+    );
+}
+
+#[test]
+fn scopes() {
+    use aidecomment::aidecomment;
+    /// Lists users
+    ///
+    /// # Security
+    ///
+    /// - oauth2
+    ///
+    /// # Scopes
+    ///
+    /// - `users:read`
+    #[aidecomment]
+    async fn list_users() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(list_users));
+    assert_eq!(operation.security[0]["oauth2"], ["users:read"]);
+}
+
+#[test]
+fn scopes_of_extractors() {
+    use aide::{gen::GenContext, openapi::Operation, OperationInput};
+    use aidecomment::aidecomment;
+    use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+    struct OAuth2;
+
+    impl OperationInput for OAuth2 {
+        fn operation_input(_: &mut GenContext, operation: &mut Operation) {
+            operation
+                .security
+                .push([("oauth2".to_owned(), Vec::new())].into());
+        }
+    }
+    #[async_trait]
+    impl<S: Send + Sync> FromRequestParts<S> for OAuth2 {
+        type Rejection = ();
+        async fn from_request_parts(_: &mut Parts, _: &S) -> Result<Self, ()> {
+            Ok(OAuth2)
+        }
+    }
+
+    /// Lists users
+    ///
+    /// # Scopes
+    ///
+    /// - `users:read`
+    #[aidecomment]
+    async fn list_users(_: OAuth2) {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(list_users));
+    assert_eq!(operation.security[0]["oauth2"], ["users:read"]);
+}
+
+#[test]
+fn servers() {
+    use aidecomment::aidecomment;
+    /// Uploads a file
+    ///
+    /// # Servers
+    ///
+    /// - https://uploads.example.com: the upload host
+    #[aidecomment]
+    async fn upload() {}
+    use aide::axum::routing::post;
+    let operation = common::operation(post(upload));
+    assert_eq!(operation.servers[0].url, "https://uploads.example.com");
+    assert_eq!(
+        operation.servers[0].description.as_deref(),
+        Some("the upload host")
+    );
+}
+
+#[test]
+fn callbacks() {
+    use aidecomment::aidecomment;
+    /// Starts a job
+    ///
+    /// # Webhooks
+    ///
+    /// - jobDone: POST {$request.body#/callbackUrl} sent when the job is done
+    #[aidecomment]
+    async fn start_job() {}
+    use aide::{axum::routing::post, openapi::ReferenceOr};
+    let operation = common::operation(post(start_job));
+    let ReferenceOr::Item(callback) = &operation.callbacks["jobDone"] else {
+        panic!()
+    };
+    let path = callback["{$request.body#/callbackUrl}"].as_item().unwrap();
+    assert_eq!(
+        path.post.as_ref().unwrap().description.as_deref(),
+        Some("sent when the job is done")
+    );
+}
+
+#[test]
+fn deprecated() {
+    #![allow(deprecated)]
+    use aidecomment::aidecomment;
+    /// Gets a user by name
+    #[aidecomment]
+    #[deprecated(note = "use `/users/:id` instead")]
+    async fn get_user_by_name() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(get_user_by_name));
+    assert!(operation.deprecated);
+    assert_eq!(
+        operation.description.as_deref(),
+        Some("**Deprecated:** use `/users/:id` instead")
+    );
+}
+
+#[test]
+fn since() {
+    use aidecomment::aidecomment;
+    /// Deletes a user
+    ///
+    /// # Since 1.4
+    #[aidecomment]
+    async fn delete_user() {}
+    use aide::axum::routing::delete;
+    let operation = common::operation(delete(delete_user));
+    assert_eq!(operation.extensions["x-since"], "1.4");
+    assert_eq!(operation.description.as_deref(), Some(""));
+}
+
+#[test]
+fn see_also() {
+    use aidecomment::aidecomment;
+    /// Uploads a file
+    ///
+    /// # See Also
+    ///
+    /// [Uploading files](https://example.com/guides/uploads)
+    #[aidecomment]
+    async fn upload() {}
+    use aide::axum::routing::post;
+    let operation = common::operation(post(upload));
+    let external_docs = operation.external_docs.unwrap();
+    assert_eq!(external_docs.url, "https://example.com/guides/uploads");
+    assert_eq!(
+        external_docs.description.as_deref(),
+        Some("Uploading files")
+    );
+    assert_eq!(operation.description.as_deref(), Some(""));
+}
+
+#[test]
+fn changelog() {
+    use aidecomment::aidecomment;
+    /// Lists users
+    ///
+    /// # Changelog
+    ///
+    /// - 1.2: added the `limit` parameter
+    /// - 1.0: added
+    #[aidecomment]
+    async fn list_users() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(list_users));
+    let changelog = &operation.extensions["x-changelog"];
+    assert_eq!(changelog[0]["version"], "1.2");
+    assert_eq!(changelog[0]["description"], "added the `limit` parameter");
+    assert_eq!(changelog[1]["version"], "1.0");
+    assert_eq!(operation.description.as_deref(), Some(""));
+}
+
+#[test]
+fn rate_limits() {
+    use aidecomment::aidecomment;
+    /// Searches users
+    ///
+    /// # Rate Limits
+    ///
+    /// - 100 requests/minute per token
+    /// - 1000 requests per day
+    #[aidecomment]
+    async fn search_users() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(search_users));
+    let limits = &operation.extensions["x-rate-limit"];
+    assert_eq!(limits[0]["limit"], 100);
+    assert_eq!(limits[0]["period"], "minute");
+    assert_eq!(limits[0]["scope"], "token");
+    assert!(limits[1]["scope"].is_null());
+    assert_eq!(
+        operation.description.as_deref(),
+        Some("Rate limits: 100 requests per minute per token, 1000 requests per day."),
+    );
+}
+
+#[test]
+fn permissions() {
+    use aidecomment::aidecomment;
+    /// Deletes a user
+    ///
+    /// # Permissions
+    ///
+    /// - `admin`: only administrators can delete users
+    #[aidecomment]
+    async fn delete_user() {}
+    use aide::axum::routing::delete;
+    let operation = common::operation(delete(delete_user));
+    assert_eq!(operation.extensions["x-permissions"][0], "admin");
+}
+
+#[test]
+fn panics() {
+    use aidecomment::aidecomment;
+    /// Gets a user
+    ///
+    /// Looks up the user by their id.
+    ///
+    /// # Panics
+    ///
+    /// If the database connection was not set up.
+    #[aidecomment]
+    async fn get_user() {}
+    use aide::{axum::routing::get, openapi::StatusCode};
+    let operation = common::operation(get(get_user));
+    assert_eq!(
+        operation.description.as_deref(),
+        Some("Looks up the user by their id.")
+    );
+    assert!(!operation
+        .responses
+        .unwrap()
+        .responses
+        .contains_key(&StatusCode::Code(500)));
+}
+
+#[test]
+fn rustdoc_sections() {
+    use aidecomment::aidecomment;
+    /// Gets a user
+    ///
+    /// # Panics
+    ///
+    /// If the database connection was not set up.
+    #[aidecomment]
+    async fn get_user() {}
+    use aide::axum::routing::get;
+    let operation = common::operation(get(get_user));
+    assert_eq!(operation.description.as_deref(), Some(""));
+}